@@ -0,0 +1,420 @@
+use crate::board::Board;
+use crate::matches::Match;
+use crate::solver::{missing_words, solve_with_stats};
+use crate::trie::Trie;
+use crate::utils::{read_board, read_target_words};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// A board file and the word list that goes with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PuzzleFiles {
+    pub name: String,
+    pub board: PathBuf,
+    pub words: PathBuf,
+}
+
+/// Find the puzzles of a directory, named like `src/input`: `board.txt` goes with `words.txt`,
+/// and `board_<name>.txt` with `<name>.txt`. Boards without a word list are skipped.
+/// # Example
+/// ```
+/// use std::path::Path;
+/// use word_search_solver::batch::discover;
+/// let puzzles = discover(Path::new("src/input")).unwrap();
+/// let names: Vec<&str> = puzzles.iter().map(|puzzle| puzzle.name.as_str()).collect();
+/// assert_eq!(names, vec!["board", "months"]);
+/// assert_eq!(puzzles[1].words, Path::new("src/input/months.txt"));
+/// ```
+pub fn discover(dir: &Path) -> Result<Vec<PuzzleFiles>, Box<dyn Error>> {
+    let mut puzzles = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        if let Some(puzzle) = puzzle_of_board(&entry?.path()) {
+            puzzles.push(puzzle);
+        }
+    }
+    puzzles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(puzzles)
+}
+
+// The puzzle of a board file named like `discover` expects, when its word list is there
+fn puzzle_of_board(board: &Path) -> Option<PuzzleFiles> {
+    let dir = board.parent().unwrap_or(Path::new(""));
+    let (name, words) = match board.file_name()?.to_str()? {
+        "board.txt" => ("board".to_string(), dir.join("words.txt")),
+        file_name => {
+            let name = file_name.strip_prefix("board_")?.strip_suffix(".txt")?;
+            (name.to_string(), dir.join(format!("{}.txt", name)))
+        }
+    };
+    words.is_file().then(|| PuzzleFiles {
+        name,
+        board: board.to_path_buf(),
+        words,
+    })
+}
+
+/// Find the puzzle a single path stands for: the first puzzle of a directory, or a board file
+/// named like `discover` expects next to its word list
+/// # Example
+/// ```
+/// use std::path::Path;
+/// use word_search_solver::batch::puzzle_at;
+/// let puzzle = puzzle_at(Path::new("src/input/board_months.txt")).unwrap();
+/// assert_eq!(puzzle.words, Path::new("src/input/months.txt"));
+/// assert_eq!(puzzle_at(Path::new("src/input")).unwrap().name, "board");
+/// assert!(puzzle_at(Path::new("src/input/months.txt")).is_err());
+/// ```
+pub fn puzzle_at(path: &Path) -> Result<PuzzleFiles, Box<dyn Error>> {
+    if path.is_dir() {
+        return discover(path)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("No puzzle found in {}", path.display()).into());
+    }
+    if !path.is_file() {
+        return Err(format!("{} not found", path.display()).into());
+    }
+    puzzle_of_board(path).ok_or_else(|| {
+        format!(
+            "{} is not a board file with its word list next to it",
+            path.display()
+        )
+        .into()
+    })
+}
+
+/// Read a manifest listing one `<board> <words>` pair per line, relative to the manifest.
+/// Blank lines and lines starting with `#` are ignored, the puzzle is named after its board file.
+pub fn read_manifest(path: &Path) -> Result<Vec<PuzzleFiles>, Box<dyn Error>> {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut puzzles = Vec::new();
+    for (line_idx, line) in fs::read_to_string(path)?.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let [board, words] = line.split_whitespace().collect::<Vec<_>>()[..] else {
+            return Err(format!(
+                "{}:{}: expected `<board> <words>`",
+                path.display(),
+                line_idx + 1
+            )
+            .into());
+        };
+        let (board, words) = (dir.join(board), dir.join(words));
+        for file in [&board, &words] {
+            if !file.is_file() {
+                return Err(format!(
+                    "{}:{}: {} not found",
+                    path.display(),
+                    line_idx + 1,
+                    file.display()
+                )
+                .into());
+            }
+        }
+        let name = board
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        puzzles.push(PuzzleFiles { name, board, words });
+    }
+    Ok(puzzles)
+}
+
+/// Outcome of solving one puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PuzzleReport {
+    pub name: String,
+    pub words: usize,
+    /// Distinct target words found on the board
+    pub found: usize,
+    pub missing: Vec<String>,
+    /// Words found in more than one place, which makes the puzzle ambiguous
+    pub ambiguous: Vec<String>,
+    pub steps: usize,
+    pub elapsed: Duration,
+    /// Why the puzzle could not be solved, like a board without letters
+    pub error: Option<String>,
+}
+impl PuzzleReport {
+    /// Report a puzzle that could not be loaded, none of its words are found
+    pub fn failed(name: &str, words: usize, error: String) -> Self {
+        PuzzleReport {
+            name: name.to_string(),
+            words,
+            found: 0,
+            missing: Vec::new(),
+            ambiguous: Vec::new(),
+            steps: 0,
+            elapsed: Duration::ZERO,
+            error: Some(error),
+        }
+    }
+}
+
+/// Solve one puzzle with the headless solver and check its words
+/// # Example
+/// ```
+/// use word_search_solver::batch::solve_puzzle;
+/// use word_search_solver::board::Board;
+/// let board = Board::new(&vec![vec!['a', 'b', 'a'], vec!['c', 'd', 'e']]);
+/// let words = ["ab", "cd", "xyz"].map(String::from).to_vec();
+/// let report = solve_puzzle("tiny", &board, &words);
+/// assert_eq!(report.found, 2);
+/// assert_eq!(report.missing, vec!["xyz"]);
+/// // "ab" reads from the first cell to the right and from the last one to the left
+/// assert_eq!(report.ambiguous, vec!["ab"]);
+/// ```
+pub fn solve_puzzle(name: &str, board: &Board, words: &[String]) -> PuzzleReport {
+    let started = Instant::now();
+    let trie = Trie::from(&words.iter().map(String::as_str).collect());
+    let (found, stats) = solve_with_stats(board, &trie);
+    let elapsed = started.elapsed();
+    let missing = missing_words(words, &found);
+    let mut ambiguous: Vec<String> = Vec::new();
+    for word in words {
+        if !ambiguous.contains(word) && placements(&found, word).len() > 1 {
+            ambiguous.push(word.clone());
+        }
+    }
+    PuzzleReport {
+        name: name.to_string(),
+        words: words.len(),
+        found: words.len() - missing.len(),
+        missing,
+        ambiguous,
+        steps: stats.steps,
+        elapsed,
+        error: None,
+    }
+}
+
+// Load and solve the puzzle of a batch, a board or word list that does not load is reported
+// instead of solved, without stopping the rest of the batch
+fn report_puzzle(puzzle: &PuzzleFiles) -> PuzzleReport {
+    let words = match read_target_words(&puzzle.words) {
+        Ok(words) => words,
+        Err(error) => return PuzzleReport::failed(&puzzle.name, 0, error.to_string()),
+    };
+    match read_board(&puzzle.board) {
+        Ok(board) => solve_puzzle(&puzzle.name, &board, &words),
+        Err(error) => PuzzleReport::failed(&puzzle.name, words.len(), error.to_string()),
+    }
+}
+
+// Distinct cells spelling the word, a palindrome or a single letter is found several times on the same cells
+fn placements(found: &[Match], word: &str) -> Vec<((usize, usize), (usize, usize))> {
+    let mut placements: Vec<((usize, usize), (usize, usize))> = found
+        .iter()
+        .filter(|word_match| word_match.word == word)
+        .map(|word_match| {
+            let (start, end) = (word_match.start, word_match.end);
+            (start.min(end), start.max(end))
+        })
+        .collect();
+    placements.sort();
+    placements.dedup();
+    placements
+}
+
+/// Reports of every puzzle of a batch
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BatchReport {
+    pub puzzles: Vec<PuzzleReport>,
+    pub elapsed: Duration,
+}
+impl BatchReport {
+    /// Load and solve every puzzle, reported in the given order.
+    /// With the `parallel` feature the puzzles are solved one per thread at a time.
+    pub fn run(puzzles: &[PuzzleFiles]) -> Self {
+        let started = Instant::now();
+        #[cfg(feature = "parallel")]
+        let puzzles = puzzles.par_iter().map(report_puzzle).collect();
+        #[cfg(not(feature = "parallel"))]
+        let puzzles = puzzles.iter().map(report_puzzle).collect();
+        BatchReport {
+            puzzles,
+            elapsed: started.elapsed(),
+        }
+    }
+    /// Puzzles where every word is found exactly once
+    pub fn valid_count(&self) -> usize {
+        self.puzzles
+            .iter()
+            .filter(|puzzle| {
+                puzzle.error.is_none() && puzzle.missing.is_empty() && puzzle.ambiguous.is_empty()
+            })
+            .count()
+    }
+    /// Write the report as JSON, timings are in milliseconds
+    pub fn to_json(&self) -> String {
+        let puzzles: Vec<String> = self
+            .puzzles
+            .iter()
+            .map(|puzzle| {
+                format!(
+                    "    {{\"name\": {}, \"words\": {}, \"found\": {}, \"missing\": {}, \"ambiguous\": {}, \"steps\": {}, \"elapsed_ms\": {:.3}, \"error\": {}}}",
+                    json_string(&puzzle.name),
+                    puzzle.words,
+                    puzzle.found,
+                    json_strings(&puzzle.missing),
+                    json_strings(&puzzle.ambiguous),
+                    puzzle.steps,
+                    puzzle.elapsed.as_secs_f64() * 1000.0,
+                    puzzle.error.as_deref().map_or("null".to_string(), json_string)
+                )
+            })
+            .collect();
+        format!(
+            "{{\n  \"puzzles\": [\n{}\n  ],\n  \"total\": {},\n  \"valid\": {},\n  \"elapsed_ms\": {:.3}\n}}\n",
+            puzzles.join(",\n"),
+            self.puzzles.len(),
+            self.valid_count(),
+            self.elapsed.as_secs_f64() * 1000.0
+        )
+    }
+    /// Describe the batch for a person, one line per puzzle and a total
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        for puzzle in self.puzzles.iter() {
+            if let Some(error) = &puzzle.error {
+                summary.push_str(&format!("{}: not solved, {}\n", puzzle.name, error));
+                continue;
+            }
+            summary.push_str(&format!(
+                "{}: {}/{} words found, {} missing, {} ambiguous, {} steps in {:.1} ms\n",
+                puzzle.name,
+                puzzle.found,
+                puzzle.words,
+                puzzle.missing.len(),
+                puzzle.ambiguous.len(),
+                puzzle.steps,
+                puzzle.elapsed.as_secs_f64() * 1000.0
+            ));
+            if !puzzle.missing.is_empty() {
+                summary.push_str(&format!("  missing: {}\n", puzzle.missing.join(", ")));
+            }
+            if !puzzle.ambiguous.is_empty() {
+                summary.push_str(&format!("  ambiguous: {}\n", puzzle.ambiguous.join(", ")));
+            }
+        }
+        summary.push_str(&format!(
+            "{} of {} puzzles valid in {:.1} ms\n",
+            self.valid_count(),
+            self.puzzles.len(),
+            self.elapsed.as_secs_f64() * 1000.0
+        ));
+        summary
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_strings(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|value| json_string(value)).collect();
+    format!("[{}]", values.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_of_a_batch() {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let valid = solve_puzzle("valid", &board, &["abc", "ei"].map(String::from));
+        let words = ["aba", "e", "q\"uote"].map(String::from);
+        let invalid = solve_puzzle("in\\valid", &board, &words);
+        // A single letter is checked in every direction, it is still one placement
+        assert!(invalid.ambiguous.is_empty());
+        assert_eq!(invalid.missing, vec!["aba", "q\"uote"]);
+        let report = BatchReport {
+            puzzles: vec![valid, invalid],
+            elapsed: Duration::from_millis(3),
+        };
+        assert_eq!(report.valid_count(), 1);
+        let json = report.to_json();
+        assert!(json.contains("\"name\": \"in\\\\valid\""));
+        assert!(json.contains("\"missing\": [\"aba\", \"q\\\"uote\"]"));
+        assert!(json.contains("\"total\": 2,\n  \"valid\": 1,\n  \"elapsed_ms\": 3.000"));
+        let summary = report.summary();
+        assert!(summary.starts_with("valid: 2/2 words found, 0 missing, 0 ambiguous"));
+        assert!(summary.contains("  missing: aba, q\"uote\n"));
+        assert!(summary.ends_with("1 of 2 puzzles valid in 3.0 ms\n"));
+    }
+    #[test]
+    fn test_manifest_paths_are_relative_to_the_manifest() {
+        let dir = std::env::temp_dir().join("word_search_batch_manifest");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("grid.txt"), "ab\ncd\n").unwrap();
+        fs::write(dir.join("list.txt"), "ab\n").unwrap();
+        let manifest = dir.join("manifest.txt");
+        fs::write(&manifest, "# puzzles\n\ngrid.txt list.txt\n").unwrap();
+        let puzzles = read_manifest(&manifest).unwrap();
+        assert_eq!(
+            puzzles,
+            vec![PuzzleFiles {
+                name: "grid".to_string(),
+                board: dir.join("grid.txt"),
+                words: dir.join("list.txt"),
+            }]
+        );
+        let report = BatchReport::run(&puzzles);
+        assert_eq!(report.valid_count(), 1);
+        assert!(report.to_json().contains("\"error\": null"));
+
+        // A board without letters is reported, the rest of the batch is still solved
+        fs::write(dir.join("empty.txt"), "\n").unwrap();
+        fs::write(&manifest, "empty.txt list.txt\ngrid.txt list.txt\n").unwrap();
+        let report = BatchReport::run(&read_manifest(&manifest).unwrap());
+        assert_eq!(report.valid_count(), 1);
+        let error = report.puzzles[0].error.as_deref().unwrap();
+        assert!(error.ends_with("empty.txt: The board has no letters"));
+        assert_eq!(report.puzzles[1].error, None);
+        assert!(report.to_json().contains("no letters\"}"));
+        assert!(report.summary().starts_with("empty: not solved, "));
+
+        // So is a word list that is not UTF-8
+        fs::write(dir.join("latin1.txt"), b"caf\xe9\n").unwrap();
+        fs::write(&manifest, "grid.txt latin1.txt\ngrid.txt list.txt\n").unwrap();
+        let report = BatchReport::run(&read_manifest(&manifest).unwrap());
+        assert_eq!(report.valid_count(), 1);
+        let error = report.puzzles[0].error.as_deref().unwrap();
+        assert!(error.contains("latin1.txt: "));
+        assert_eq!(report.puzzles[0].words, 0);
+        assert_eq!(report.puzzles[1].error, None);
+
+        fs::write(&manifest, "grid.txt\n").unwrap();
+        assert!(read_manifest(&manifest).is_err());
+        fs::write(&manifest, "grid.txt nowhere.txt\n").unwrap();
+        let error = read_manifest(&manifest).unwrap_err().to_string();
+        assert!(error.ends_with("nowhere.txt not found"));
+    }
+}
@@ -1,9 +1,13 @@
+use crate::matches::Match;
 use crate::state::search_state::SearchState;
 use crate::trie::Trie;
+#[cfg(feature = "ggez")]
 use ggez::glam::Vec2;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::slice::Iter;
-#[derive(Debug)]
-
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WordPosition {
     start: (usize, usize),
     end: (usize, usize),
@@ -12,6 +16,12 @@ impl WordPosition {
     pub fn new(start: (usize, usize), end: (usize, usize)) -> Self {
         WordPosition { start, end }
     }
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+    pub fn end(&self) -> (usize, usize) {
+        self.end
+    }
     /// Convert from 2D position to 1D position
     /// For example: an 5x5 board, the position (1,2) will be converted to 7, and (4,4) will be converted to 24(0-indexed)
     /// # Arguments
@@ -33,7 +43,33 @@ impl WordPosition {
         let end = end_i * board_size + end_j;
         (start, end)
     }
+    /// Get every cell covered by the word, from start to end
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::WordPosition;
+    /// let word_pos = WordPosition::new((2, 0), (0, 2));
+    /// assert_eq!(word_pos.cells(), vec![(2, 0), (1, 1), (0, 2)]);
+    /// let word_pos = WordPosition::new((1, 1), (1, 1));
+    /// assert_eq!(word_pos.cells(), vec![(1, 1)]);
+    /// ```
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        let di = (self.end.0 as i32 - self.start.0 as i32).signum();
+        let dj = (self.end.1 as i32 - self.start.1 as i32).signum();
+        let length = usize::max(
+            self.start.0.abs_diff(self.end.0),
+            self.start.1.abs_diff(self.end.1),
+        ) as i32;
+        (0..=length)
+            .map(|k| {
+                (
+                    (self.start.0 as i32 + di * k) as usize,
+                    (self.start.1 as i32 + dj * k) as usize,
+                )
+            })
+            .collect()
+    }
     /// Convert the raw usize position to a Vec2 tuple
+    #[cfg(feature = "ggez")]
     pub fn to_vec2(&self) -> (Vec2, Vec2) {
         let start = Vec2::new(self.start.1 as f32, self.start.0 as f32);
         let end = Vec2::new(self.end.1 as f32, self.end.0 as f32);
@@ -41,6 +77,13 @@ impl WordPosition {
     }
 }
 
+/// Serialized as its rows of letters
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "Vec<Vec<char>>", into = "Vec<Vec<char>>")
+)]
 pub struct Board {
     pub letters: Vec<Vec<char>>,
     cols: usize,
@@ -48,7 +91,7 @@ pub struct Board {
 }
 impl Board {
     pub fn new(letters: &Vec<Vec<char>>) -> Self {
-        let cols = letters.get(0).unwrap().len();
+        let cols = letters.first().unwrap().len();
         let rows = letters.len();
         Board {
             letters: letters.to_owned(),
@@ -165,7 +208,7 @@ impl Board {
             0,
         ))
     }
-    pub fn check_state(&self, state: &mut SearchState, trie: &Trie) -> Option<WordPosition> {
+    pub fn check_state(&self, state: &mut SearchState, trie: &Trie) -> Option<Match> {
         let (i, j) = state.position;
         let distance = state.distance;
         let direction = state.direction;
@@ -178,11 +221,7 @@ impl Board {
                 Some(_) => state.feasible = true,
             }
             if trie.search(&string) {
-                let word_position = WordPosition::new(
-                    (i, j),
-                    Board::get_pos_from_direction(i, j, &direction, distance).unwrap_or_default(),
-                );
-                return Some(word_position);
+                return Match::on_board(self, trie, (i, j), direction, distance);
             }
         }
         None
@@ -277,6 +316,62 @@ impl Board {
     pub fn get_cols(&self) -> usize {
         self.cols
     }
+    /// Change the letter of a cell, nothing happens when the cell is not on the board
+    pub fn set_letter(&mut self, i: usize, j: usize, letter: char) {
+        if let Some(cell) = self.letters.get_mut(i).and_then(|row| row.get_mut(j)) {
+            *cell = letter;
+        }
+    }
+    /// Insert a row filled with the given letter before row `i`, at the bottom when `i` is the row count
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// let mut board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+    /// board.insert_row(1, 'x');
+    /// board.insert_col(2, 'y');
+    /// assert_eq!(board.letters, vec![vec!['a', 'b', 'y'], vec!['x', 'x', 'y'], vec!['c', 'd', 'y']]);
+    /// assert!(board.remove_row(0));
+    /// assert!(board.remove_col(0));
+    /// assert_eq!((board.get_rows(), board.get_cols()), (2, 2));
+    /// assert_eq!(board.letters, vec![vec!['x', 'y'], vec!['d', 'y']]);
+    /// ```
+    pub fn insert_row(&mut self, i: usize, letter: char) {
+        let i = i.min(self.rows);
+        self.letters.insert(i, vec![letter; self.cols]);
+        self.rows += 1;
+    }
+    /// Insert a column filled with the given letter before column `j`, on the right when `j` is the column count
+    pub fn insert_col(&mut self, j: usize, letter: char) {
+        let j = j.min(self.cols);
+        for row in self.letters.iter_mut() {
+            row.insert(j, letter);
+        }
+        self.cols += 1;
+    }
+    /// Remove row `i`, the last row is kept so the board is never empty
+    /// # Returns
+    /// * `bool` - Whether the row was removed
+    pub fn remove_row(&mut self, i: usize) -> bool {
+        if self.rows <= 1 || i >= self.rows {
+            return false;
+        }
+        self.letters.remove(i);
+        self.rows -= 1;
+        true
+    }
+    /// Remove column `j`, the last column is kept so the board is never empty
+    /// # Returns
+    /// * `bool` - Whether the column was removed
+    pub fn remove_col(&mut self, j: usize) -> bool {
+        if self.cols <= 1 || j >= self.cols {
+            return false;
+        }
+        for row in self.letters.iter_mut() {
+            row.remove(j);
+        }
+        self.cols -= 1;
+        true
+    }
 
     /// Get the letter in the board at a given position, retrun None if the position is invalid or out of bound
     ///
@@ -299,7 +394,6 @@ impl Board {
     /// assert_eq!(board.get_letter(Some(3), Some(0)), None);
     /// ```
     ///
-
     pub fn get_letter(&self, x: Option<usize>, y: Option<usize>) -> Option<String> {
         let x = x?;
         let y = y?;
@@ -388,8 +482,46 @@ impl Board {
         }
     }
 }
+/// Build a board from its rows, checking that it is not empty and that every row has the same length
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// let board = Board::try_from(vec![vec!['a', 'b'], vec!['c', 'd']]).unwrap();
+/// assert_eq!(board.get_cols(), 2);
+/// assert!(Board::try_from(vec![vec!['a', 'b'], vec!['c']]).is_err());
+/// assert!(Board::try_from(vec![]).is_err());
+/// ```
+impl TryFrom<Vec<Vec<char>>> for Board {
+    type Error = String;
+    fn try_from(letters: Vec<Vec<char>>) -> Result<Self, Self::Error> {
+        let cols = letters.first().map_or(0, Vec::len);
+        if cols == 0 {
+            return Err("The board has no letters".to_string());
+        }
+        if let Some(row) = letters.iter().position(|row| row.len() != cols) {
+            return Err(format!(
+                "Row {} has {} letters instead of {}",
+                row + 1,
+                letters[row].len(),
+                cols
+            ));
+        }
+        Ok(Board::new(&letters))
+    }
+}
+impl From<Board> for Vec<Vec<char>> {
+    fn from(board: Board) -> Self {
+        board.letters
+    }
+}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// The 8 directions a word can go, declared in the order the search tries them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(rename_all = "kebab-case", try_from = "String")
+)]
 pub enum Direction {
     #[default]
     Up,
@@ -398,13 +530,103 @@ pub enum Direction {
     Right,
     UpRight,
     UpLeft,
-    DownRight,
     DownLeft,
+    DownRight,
 }
 
+/// Read a direction from its name or its compass point, in any case for the compass point
+/// # Example
+/// ```
+/// use word_search_solver::board::Direction;
+/// assert_eq!(Direction::try_from("up-left".to_string()), Ok(Direction::UpLeft));
+/// assert_eq!(Direction::try_from("se".to_string()), Ok(Direction::DownRight));
+/// assert!(Direction::try_from("sideways".to_string()).is_err());
+/// ```
+impl TryFrom<String> for Direction {
+    type Error = String;
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Direction::from_name(&name)
+            .or_else(|| Direction::from_compass(&name))
+            .ok_or_else(|| format!("Unknown direction {}", name))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CoordDiff(pub i32, pub i32);
 
 impl Direction {
+    /// Stable lowercase name of the direction, used in trace files
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Direction;
+    /// assert_eq!(Direction::DownLeft.name(), "down-left");
+    /// assert_eq!(Direction::from_name("down-left"), Some(Direction::DownLeft));
+    /// assert_eq!(Direction::from_name("sideways"), None);
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+            Direction::UpRight => "up-right",
+            Direction::UpLeft => "up-left",
+            Direction::DownRight => "down-right",
+            Direction::DownLeft => "down-left",
+        }
+    }
+    pub fn from_name(name: &str) -> Option<Direction> {
+        Direction::iterator()
+            .find(|direction| direction.name() == name)
+            .copied()
+    }
+    /// Compass point of the direction, with up as north
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Direction;
+    /// assert_eq!(Direction::DownLeft.compass(), "SW");
+    /// assert_eq!(Direction::from_compass("ne"), Some(Direction::UpRight));
+    /// assert_eq!(Direction::from_compass("NNE"), None);
+    /// ```
+    pub fn compass(&self) -> &'static str {
+        match self {
+            Direction::Up => "N",
+            Direction::Down => "S",
+            Direction::Left => "W",
+            Direction::Right => "E",
+            Direction::UpRight => "NE",
+            Direction::UpLeft => "NW",
+            Direction::DownRight => "SE",
+            Direction::DownLeft => "SW",
+        }
+    }
+    pub fn from_compass(compass: &str) -> Option<Direction> {
+        Direction::iterator()
+            .find(|direction| direction.compass().eq_ignore_ascii_case(compass))
+            .copied()
+    }
+    /// Get the direction going from one cell to another, if they are on the same row, column or diagonal
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Direction;
+    /// assert_eq!(Direction::between((2, 0), (0, 2)), Some(Direction::UpRight));
+    /// assert_eq!(Direction::between((1, 1), (1, 0)), Some(Direction::Left));
+    /// assert_eq!(Direction::between((0, 0), (1, 2)), None);
+    /// assert_eq!(Direction::between((1, 1), (1, 1)), None);
+    /// ```
+    pub fn between(start: (usize, usize), end: (usize, usize)) -> Option<Direction> {
+        let di = end.0 as i32 - start.0 as i32;
+        let dj = end.1 as i32 - start.1 as i32;
+        if (di == 0 && dj == 0) || (di != 0 && dj != 0 && di.abs() != dj.abs()) {
+            return None;
+        }
+        Direction::iterator()
+            .find(|direction| {
+                let CoordDiff(i, j) = direction.to_coord_diff();
+                i == di.signum() && j == dj.signum()
+            })
+            .copied()
+    }
     pub fn to_coord_diff(&self) -> CoordDiff {
         match self {
             Direction::Up => CoordDiff(-1, 0),
@@ -486,4 +708,43 @@ mod tests {
         assert_eq!(Board::add(0, -1), None);
         assert_eq!(Board::add(2, -1), Some(1));
     }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(json, r#"[["a","b"],["c","d"]]"#);
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+        assert!(serde_json::from_str::<Board>(r#"[["a","b"],["c"]]"#).is_err());
+        assert!(serde_json::from_str::<Board>("[]").is_err());
+
+        for direction in Direction::iterator() {
+            let json = serde_json::to_string(direction).unwrap();
+            assert_eq!(json, format!("\"{}\"", direction.name()));
+            let compass = format!("\"{}\"", direction.compass());
+            assert_eq!(
+                serde_json::from_str::<Direction>(&json).unwrap(),
+                *direction
+            );
+            assert_eq!(
+                serde_json::from_str::<Direction>(&compass).unwrap(),
+                *direction
+            );
+            // Compass points are read in any case, like `Direction::from_compass`
+            let compass = compass.to_lowercase();
+            assert_eq!(
+                serde_json::from_str::<Direction>(&compass).unwrap(),
+                *direction
+            );
+        }
+        assert!(serde_json::from_str::<Direction>("\"sideways\"").is_err());
+
+        let word_pos = WordPosition::new((2, 0), (0, 2));
+        let json = serde_json::to_string(&word_pos).unwrap();
+        assert_eq!(json, r#"{"start":[2,0],"end":[0,2]}"#);
+        assert_eq!(
+            serde_json::from_str::<WordPosition>(&json).unwrap(),
+            word_pos
+        );
+    }
 }
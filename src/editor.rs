@@ -0,0 +1,222 @@
+use crate::board::{Board, Direction};
+use crate::matches::Match;
+use crate::render::ansi::render_board;
+use crate::solver::{missing_words, solve};
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Letter of the cells of a new row or column
+pub const NEW_CELL: char = 'A';
+
+/// A puzzle being edited: letters typed on the board or in the word list, with the solver
+/// run again after every change
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Editor {
+    pub board: Board,
+    pub words: Vec<String>,
+    /// Cell the typed letters go to when no word is selected
+    pub cursor: (usize, usize),
+    /// Word of the list the typed letters go to
+    pub selected_word: Option<usize>,
+    /// Every placement of the words on the board
+    pub found: Vec<Match>,
+    /// Words that are not on the board
+    pub missing: Vec<String>,
+    /// Whether there are changes that are not saved
+    pub dirty: bool,
+}
+impl Editor {
+    pub fn new(board: Board, words: Vec<String>) -> Self {
+        let mut editor = Editor {
+            board,
+            words,
+            cursor: (0, 0),
+            selected_word: None,
+            found: Vec::new(),
+            missing: Vec::new(),
+            dirty: false,
+        };
+        editor.resolve();
+        editor.dirty = false;
+        editor
+    }
+    /// Move the cursor to a cell, and leave the word being typed
+    pub fn click_cell(&mut self, cell: (usize, usize)) {
+        if cell.0 < self.board.get_rows() && cell.1 < self.board.get_cols() {
+            self.cursor = cell;
+            self.selected_word = None;
+        }
+    }
+    /// Move the cursor one cell, it stays on the board
+    pub fn move_cursor(&mut self, direction: Direction) {
+        let (i, j) = self.cursor;
+        if let Some((i, j)) = Board::get_pos_from_direction(i, j, &direction, 1) {
+            self.click_cell((i, j));
+        }
+    }
+    /// Type a letter in the selected word, or in the cursor cell and move to the next cell.
+    /// Letters are made uppercase like the bundled puzzles, anything else is ignored.
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::editor::Editor;
+    /// let board = Board::new(&vec![vec!['A', 'B'], vec!['C', 'D']]);
+    /// let mut editor = Editor::new(board, vec!["AB".to_string(), "BD".to_string()]);
+    /// assert!(editor.missing.is_empty());
+    /// editor.click_cell((0, 1));
+    /// editor.type_letter('x');
+    /// assert_eq!(editor.board.letters[0], vec!['A', 'X']);
+    /// assert_eq!(editor.cursor, (1, 0));
+    /// assert_eq!(editor.missing, vec!["AB", "BD"]);
+    /// editor.select_word(Some(0));
+    /// editor.backspace();
+    /// editor.type_letter('x');
+    /// assert_eq!(editor.words[0], "AX");
+    /// assert_eq!(editor.missing, vec!["BD"]);
+    /// ```
+    pub fn type_letter(&mut self, letter: char) {
+        if !letter.is_alphabetic() {
+            return;
+        }
+        let letter = letter.to_uppercase().next().unwrap_or(letter);
+        match self.selected_word {
+            Some(idx) => self.words[idx].push(letter),
+            None => {
+                let (i, j) = self.cursor;
+                self.board.set_letter(i, j, letter);
+                self.cursor = self.board.next_pos(i, j).unwrap_or(self.cursor);
+            }
+        }
+        self.resolve();
+    }
+    /// Erase the last letter of the selected word, or move the cursor back one cell
+    pub fn backspace(&mut self) {
+        match self.selected_word {
+            Some(idx) => {
+                self.words[idx].pop();
+                self.resolve();
+            }
+            // A cell always has a letter, going back lets it be typed again
+            None => {
+                let (i, j) = self.cursor;
+                let cols = self.board.get_cols();
+                let idx = (i * cols + j).saturating_sub(1);
+                self.cursor = (idx / cols, idx % cols);
+            }
+        }
+    }
+    /// Insert a row under the cursor
+    pub fn insert_row(&mut self) {
+        self.board.insert_row(self.cursor.0 + 1, NEW_CELL);
+        self.resolve();
+    }
+    /// Remove the row of the cursor, the last one is kept
+    pub fn remove_row(&mut self) {
+        if self.board.remove_row(self.cursor.0) {
+            self.cursor.0 = self.cursor.0.min(self.board.get_rows() - 1);
+            self.resolve();
+        }
+    }
+    /// Insert a column on the right of the cursor
+    pub fn insert_col(&mut self) {
+        self.board.insert_col(self.cursor.1 + 1, NEW_CELL);
+        self.resolve();
+    }
+    /// Remove the column of the cursor, the last one is kept
+    pub fn remove_col(&mut self) {
+        if self.board.remove_col(self.cursor.1) {
+            self.cursor.1 = self.cursor.1.min(self.board.get_cols() - 1);
+            self.resolve();
+        }
+    }
+    /// Select the word the typed letters go to, `None` goes back to the board
+    pub fn select_word(&mut self, idx: Option<usize>) {
+        self.selected_word = idx.filter(|idx| *idx < self.words.len());
+    }
+    /// Add an empty word at the end of the list and select it
+    pub fn add_word(&mut self) {
+        self.words.push(String::new());
+        self.selected_word = Some(self.words.len() - 1);
+        self.dirty = true;
+    }
+    /// Remove the selected word from the list
+    pub fn remove_word(&mut self) {
+        if let Some(idx) = self.selected_word.take() {
+            self.words.remove(idx);
+            self.resolve();
+        }
+    }
+    /// Words of the list, without the one being typed when it is still empty
+    pub fn word_list(&self) -> Vec<String> {
+        self.words
+            .iter()
+            .filter(|word| !word.is_empty())
+            .cloned()
+            .collect()
+    }
+    // Solve the board again with the current words
+    fn resolve(&mut self) {
+        let words = self.word_list();
+        let trie = Trie::from(&words.iter().map(String::as_str).collect());
+        self.found = solve(&self.board, &trie);
+        self.missing = missing_words(&words, &self.found);
+        self.dirty = true;
+    }
+    /// Write the board and the word list in the format of the bundled puzzles
+    pub fn save(&mut self, board_path: &Path, words_path: &Path) -> std::io::Result<()> {
+        fs::write(board_path, render_board(&self.board))?;
+        fs::write(words_path, format!("{}\n", self.word_list().join(" ")))?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{fetch_board, fetch_target_words};
+
+    #[test]
+    fn test_resize_and_save() {
+        let board = Board::new(&vec![vec!['C', 'A', 'T'], vec!['X', 'Y', 'Z']]);
+        let mut editor = Editor::new(board, vec!["CAT".to_string()]);
+        assert!(!editor.dirty);
+        editor.click_cell((1, 2));
+        editor.insert_row();
+        editor.insert_col();
+        assert_eq!((editor.board.get_rows(), editor.board.get_cols()), (3, 4));
+        assert_eq!(editor.board.letters[2], vec!['A'; 4]);
+        // The word spelled down the new column is found as soon as it is in the list
+        editor.add_word();
+        for letter in "AAA".chars() {
+            editor.type_letter(letter);
+        }
+        assert!(editor.missing.is_empty());
+        editor.click_cell((0, 0));
+        editor.remove_col();
+        assert_eq!(editor.board.letters[0], vec!['A', 'T', 'A']);
+        assert_eq!(editor.missing, vec!["CAT"]);
+        // The cursor stays on the board when its row goes away
+        editor.click_cell((2, 2));
+        editor.remove_row();
+        assert_eq!(editor.cursor, (1, 2));
+        for _ in 0..3 {
+            editor.remove_row();
+        }
+        assert_eq!(editor.board.get_rows(), 1);
+
+        editor.add_word();
+        assert_eq!(editor.word_list(), vec!["CAT", "AAA"]);
+        let dir = std::env::temp_dir().join("word_search_editor");
+        fs::create_dir_all(&dir).unwrap();
+        let (board_path, words_path) = (dir.join("board_edited.txt"), dir.join("edited.txt"));
+        editor.save(&board_path, &words_path).unwrap();
+        assert!(!editor.dirty);
+        assert_eq!(fetch_board(&board_path), editor.board.letters);
+        assert_eq!(fetch_target_words(&words_path), vec!["CAT", "AAA"]);
+    }
+}
@@ -0,0 +1,345 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::matches::Match;
+use crate::solver::{missing_words, solve};
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Points earned for each letter of a found word
+pub const POINTS_PER_LETTER: u32 = 10;
+/// Points lost for a selection that is not a target word
+pub const WRONG_SELECTION_PENALTY: u32 = 5;
+/// Points lost for each hint, from the weakest to the strongest
+pub const HINT_COSTS: [u32; 3] = [5, 10, 20];
+
+/// Outcome of a selection made by the player
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Selection {
+    /// A target word that was not found yet
+    Found(String),
+    AlreadyFound,
+    /// A straight line that does not spell a target word
+    Wrong,
+    /// The two cells are not on the same row, column or diagonal
+    NotALine,
+    /// A single cell that is not a one-letter target word, a click without a drag is not a mistake
+    SingleCell,
+}
+
+/// Help given for a word that was not found yet, each level reveals more of its placement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Hint {
+    /// The cell of the first letter
+    FirstLetter((usize, usize)),
+    /// The cell of the first letter and the direction the word goes
+    Direction((usize, usize), Direction),
+    /// The whole placement
+    Placement(WordPosition),
+}
+
+/// What the player did in a game, enough to resume it later
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameProgress {
+    /// Placements found by the player, in the order they were found
+    pub found: Vec<WordPosition>,
+    pub score: u32,
+    pub mistakes: usize,
+    pub hints_used: usize,
+    /// Word the player gets hints for, and how many hints were given for it
+    pub hint: Option<(String, usize)>,
+    pub elapsed: Duration,
+}
+
+/// A game where the player looks for the words, with a timer and a score
+pub struct Game {
+    pub board: Board,
+    pub words: Vec<String>,
+    trie: Trie,
+    /// Every placement found by the solver
+    pub solution: Vec<Match>,
+    /// Words found by the player, in the order they were found
+    pub found: Vec<Match>,
+    pub score: u32,
+    pub mistakes: usize,
+    pub hints_used: usize,
+    // Placement the player gets hints for, and how many hints were given for it
+    hint: Option<(Match, usize)>,
+    started: Instant,
+    finished_after: Option<Duration>,
+}
+impl Game {
+    pub fn new(board: Board, words: Vec<String>) -> Self {
+        let trie = Trie::from(&words.iter().map(String::as_str).collect());
+        let solution = solve(&board, &trie);
+        Game {
+            board,
+            words,
+            trie,
+            solution,
+            found: Vec::new(),
+            score: 0,
+            mistakes: 0,
+            hints_used: 0,
+            hint: None,
+            started: Instant::now(),
+            finished_after: None,
+        }
+    }
+    /// Start the game over where the progress left it, the placements are found again on the board
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::game::Game;
+    /// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// let words = vec!["abc".to_string(), "ie".to_string()];
+    /// let mut game = Game::new(board.clone(), words.clone());
+    /// game.select((0, 0), (0, 2));
+    /// game.hint();
+    /// let resumed = Game::resume(board, words, &game.progress()).unwrap();
+    /// assert_eq!(resumed.found, game.found);
+    /// assert_eq!(resumed.current_hint(), game.current_hint());
+    /// assert_eq!((resumed.score, resumed.hints_used), (25, 1));
+    /// ```
+    pub fn resume(
+        board: Board,
+        words: Vec<String>,
+        progress: &GameProgress,
+    ) -> Result<Game, String> {
+        let mut game = Game::new(board, words);
+        for placement in progress.found.iter() {
+            let (start, end) = (placement.start(), placement.end());
+            if !matches!(game.select(start, end), Selection::Found(_)) {
+                return Err(format!(
+                    "No word left to find from {:?} to {:?}",
+                    start, end
+                ));
+            }
+        }
+        if let Some((word, level)) = &progress.hint {
+            let word_match = game
+                .solution
+                .iter()
+                .find(|word_match| word_match.word == *word)
+                .ok_or_else(|| format!("{} is not on the board", word))?;
+            game.hint = Some((word_match.clone(), *level));
+        }
+        game.score = progress.score;
+        game.mistakes = progress.mistakes;
+        game.hints_used = progress.hints_used;
+        // The timer goes on from the saved time
+        game.started = Instant::now()
+            .checked_sub(progress.elapsed)
+            .unwrap_or_else(Instant::now);
+        game.finished_after = game.is_finished().then_some(progress.elapsed);
+        Ok(game)
+    }
+    /// What the player did so far
+    pub fn progress(&self) -> GameProgress {
+        GameProgress {
+            found: self.found.iter().map(Match::position).collect(),
+            score: self.score,
+            mistakes: self.mistakes,
+            hints_used: self.hints_used,
+            hint: self
+                .hint
+                .as_ref()
+                .map(|(word_match, level)| (word_match.word.clone(), *level)),
+            elapsed: self.elapsed(),
+        }
+    }
+    /// Check the word spelled from `start` to `end`, in either direction, against the target words
+    /// # Arguments
+    /// * `start` - The (row, column) where the drag started
+    /// * `end` - The (row, column) where the drag ended
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::game::{Game, Selection};
+    /// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// let mut game = Game::new(board, vec!["abc".to_string(), "ie".to_string()]);
+    /// assert_eq!(game.select((0, 0), (0, 2)), Selection::Found("abc".to_string()));
+    /// assert_eq!(game.select((0, 2), (0, 0)), Selection::AlreadyFound);
+    /// assert_eq!(game.select((0, 0), (1, 2)), Selection::NotALine);
+    /// assert_eq!(game.select((0, 0), (2, 0)), Selection::Wrong);
+    /// assert_eq!(game.select((2, 2), (2, 2)), Selection::SingleCell);
+    /// assert_eq!(game.mistakes, 1);
+    /// assert_eq!(game.select((1, 1), (2, 2)), Selection::Found("ie".to_string()));
+    /// assert!(game.is_finished());
+    /// ```
+    pub fn select(&mut self, start: (usize, usize), end: (usize, usize)) -> Selection {
+        if start != end && Direction::between(start, end).is_none() {
+            return Selection::NotALine;
+        }
+        let distance = usize::max(start.0.abs_diff(end.0), start.1.abs_diff(end.1)) as i32;
+        // Players drag either way, so the word is also read backward
+        for (from, to) in [(start, end), (end, start)] {
+            let direction = Direction::between(from, to).unwrap_or_default();
+            let Some(word_match) =
+                Match::on_board(&self.board, &self.trie, from, direction, distance)
+            else {
+                continue;
+            };
+            if self.is_found(&word_match.word) {
+                return Selection::AlreadyFound;
+            }
+            let word = word_match.word.clone();
+            self.score += POINTS_PER_LETTER * word_match.length as u32;
+            self.found.push(word_match);
+            if self.is_finished() && self.finished_after.is_none() {
+                self.finished_after = Some(self.started.elapsed());
+            }
+            return Selection::Found(word);
+        }
+        if start == end {
+            return Selection::SingleCell;
+        }
+        self.mistakes += 1;
+        self.score = self.score.saturating_sub(WRONG_SELECTION_PENALTY);
+        Selection::Wrong
+    }
+    /// Give a stronger hint for the word hinted so far, or the first hint for the next word to find.
+    /// Hints come from the solver placements, so they always point at a real word.
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Board, Direction, WordPosition};
+    /// use word_search_solver::game::{Game, Hint};
+    /// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// let mut game = Game::new(board, vec!["abc".to_string()]);
+    /// game.score = 100;
+    /// assert_eq!(game.hint(), Some(Hint::FirstLetter((0, 0))));
+    /// assert_eq!(game.hint(), Some(Hint::Direction((0, 0), Direction::Right)));
+    /// let placement = WordPosition::new((0, 0), (0, 2));
+    /// assert_eq!(game.hint(), Some(Hint::Placement(placement)));
+    /// assert_eq!(game.score, 65);
+    /// assert_eq!(game.hints_used, 3);
+    /// ```
+    pub fn hint(&mut self) -> Option<Hint> {
+        let level = match self.current_hint() {
+            Some(Hint::Placement(_)) => return self.current_hint(),
+            Some(_) => self.hint.as_ref().map_or(0, |(_, level)| *level),
+            None => {
+                let word_match = self
+                    .solution
+                    .iter()
+                    .find(|word_match| !self.is_found(&word_match.word))?;
+                self.hint = Some((word_match.clone(), 0));
+                0
+            }
+        };
+        if let Some((_, hint_level)) = self.hint.as_mut() {
+            *hint_level = level + 1;
+        }
+        self.hints_used += 1;
+        self.score = self.score.saturating_sub(HINT_COSTS[level]);
+        self.current_hint()
+    }
+    /// The hint to show, until its word is found
+    pub fn current_hint(&self) -> Option<Hint> {
+        let (word_match, level) = self.hint.as_ref()?;
+        if *level == 0 || self.is_found(&word_match.word) {
+            return None;
+        }
+        let first = word_match.start;
+        match level {
+            1 => Some(Hint::FirstLetter(first)),
+            // A single letter word is found in every direction, any one points at the right cell
+            2 => Some(Hint::Direction(first, word_match.direction)),
+            _ => Some(Hint::Placement(word_match.position())),
+        }
+    }
+    /// Whether the player already found the given word
+    pub fn is_found(&self, word: &str) -> bool {
+        self.found.iter().any(|word_match| word_match.word == word)
+    }
+    /// Whether every word that is on the board was found
+    pub fn is_finished(&self) -> bool {
+        self.remaining_words().is_empty()
+    }
+    /// Target words that are on the board but not found yet
+    pub fn remaining_words(&self) -> Vec<String> {
+        let missing = missing_words(&self.words, &self.found);
+        let not_on_board = missing_words(&self.words, &self.solution);
+        missing
+            .into_iter()
+            .filter(|word| !not_on_board.contains(word))
+            .collect()
+    }
+    /// Lines of the overlay shown once every word is found
+    pub fn summary(&self) -> Vec<String> {
+        vec![
+            "Puzzle solved".to_string(),
+            format!("Score: {}", self.score),
+            format!("Time: {:.1} s", self.elapsed().as_secs_f32()),
+            format!("Wrong selections: {}", self.mistakes),
+            format!("Hints used: {}", self.hints_used),
+            "[r] play again   [l] load another puzzle   [p] watch the solver".to_string(),
+        ]
+    }
+    /// Time since the game started, stopped once it is finished
+    pub fn elapsed(&self) -> Duration {
+        self.finished_after
+            .unwrap_or_else(|| self.started.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game() -> Game {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let words = ["abc", "aei", "xyz"].map(String::from).to_vec();
+        Game::new(board, words)
+    }
+    #[test]
+    fn test_score_and_finish() {
+        let mut game = game();
+        assert_eq!(game.remaining_words(), vec!["abc", "aei"]);
+        assert_eq!(game.select((0, 0), (1, 0)), Selection::Wrong);
+        assert_eq!(game.score, 0);
+        assert_eq!(game.select((2, 2), (0, 0)), Selection::Found("aei".into()));
+        assert_eq!(game.found[0].position(), WordPosition::new((0, 0), (2, 2)));
+        assert_eq!(game.score, 30);
+        assert_eq!(game.select((1, 1), (1, 2)), Selection::Wrong);
+        assert_eq!(game.score, 25);
+        assert_eq!(game.mistakes, 2);
+        assert!(!game.is_finished());
+        // The word that is not on the board is not needed to finish
+        assert_eq!(game.select((0, 0), (0, 2)), Selection::Found("abc".into()));
+        assert!(game.is_finished());
+        assert_eq!(game.hint(), None);
+        assert_eq!(game.hints_used, 0);
+        let elapsed = game.elapsed();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(game.elapsed(), elapsed);
+    }
+    #[test]
+    fn test_hint_moves_on_once_the_word_is_found() {
+        let mut game = game();
+        assert_eq!(game.hint(), Some(Hint::FirstLetter((0, 0))));
+        // Hints cost nothing once the score is down to zero
+        assert_eq!(game.score, 0);
+        assert_eq!(game.select((0, 0), (0, 2)), Selection::Found("abc".into()));
+        assert_eq!(game.current_hint(), None);
+        // The next hint is for aei, which starts on the same cell
+        assert_eq!(game.hint(), Some(Hint::FirstLetter((0, 0))));
+        game.hint();
+        assert_eq!(
+            game.hint(),
+            Some(Hint::Placement(WordPosition::new((0, 0), (2, 2))))
+        );
+        // The strongest hint is not charged again
+        game.score = 50;
+        game.hint();
+        assert_eq!(game.score, 50);
+        assert_eq!(game.hints_used, 4);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::board::Board;
+use crate::trace::TraceStep;
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Search effort per cell: how many probes went through it, and how many probes ending on it
+/// were pruned because no target word starts with them
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Heatmap {
+    pub visits: Vec<Vec<u32>>,
+    pub prunes: Vec<Vec<u32>>,
+    // Visits of the busiest cell, kept up to date so that drawing does not look for it every frame
+    max_visits: u32,
+}
+impl Heatmap {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Heatmap {
+            visits: vec![vec![0; cols]; rows],
+            prunes: vec![vec![0; cols]; rows],
+            max_visits: 0,
+        }
+    }
+    /// Build the heatmap of the given steps, used to catch up on a replay
+    pub fn from_steps(board: &Board, trie: &Trie, steps: &[TraceStep]) -> Self {
+        let mut heatmap = Heatmap::new(board.get_rows(), board.get_cols());
+        for step in steps {
+            heatmap.record(board, trie, step);
+        }
+        heatmap
+    }
+    /// Count one step of the search
+    /// # Arguments
+    /// * `board` - The board being searched
+    /// * `trie` - The trie the prefix is checked against
+    /// * `step` - The probe checked by the step
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Board, Direction};
+    /// use word_search_solver::heatmap::Heatmap;
+    /// use word_search_solver::trace::TraceStep;
+    /// use word_search_solver::trie::Trie;
+    /// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+    /// let trie = Trie::from(&vec!["ab"]);
+    /// let mut heatmap = Heatmap::new(2, 2);
+    /// let step = |distance| TraceStep {
+    ///     position: (0, 0),
+    ///     direction: Direction::Down,
+    ///     distance,
+    ///     feasible: distance == 0,
+    ///     found: None,
+    /// };
+    /// heatmap.record(&board, &trie, &step(0));
+    /// heatmap.record(&board, &trie, &step(1));
+    /// assert_eq!(heatmap.visits, vec![vec![2, 0], vec![1, 0]]);
+    /// // "ac" is not the start of any word
+    /// assert_eq!(heatmap.prunes, vec![vec![0, 0], vec![1, 0]]);
+    /// assert_eq!(heatmap.max_visits(), 2);
+    /// // The cells past the edge of the board are not counted
+    /// heatmap.record(&board, &trie, &step(5));
+    /// assert_eq!(heatmap.visits, vec![vec![3, 0], vec![2, 0]]);
+    /// ```
+    pub fn record(&mut self, board: &Board, trie: &Trie, step: &TraceStep) {
+        let (i, j) = step.position;
+        let (rows, cols) = (self.visits.len(), self.visits.first().map_or(0, Vec::len));
+        let on_board = |cell: &(usize, usize)| cell.0 < rows && cell.1 < cols;
+        for distance in 0..=step.distance {
+            if let Some((row, col)) =
+                Board::get_pos_from_direction(i, j, &step.direction, distance).filter(on_board)
+            {
+                self.visits[row][col] += 1;
+                self.max_visits = self.max_visits.max(self.visits[row][col]);
+            }
+        }
+        let end = Board::get_pos_from_direction(i, j, &step.direction, step.distance);
+        if let Some((row, col)) = end.filter(on_board) {
+            if step.is_pruned(board, trie) {
+                self.prunes[row][col] += 1;
+            }
+        }
+    }
+    pub fn clear(&mut self) {
+        *self = Heatmap::new(self.visits.len(), self.visits.first().map_or(0, Vec::len));
+    }
+    pub fn max_visits(&self) -> u32 {
+        self.max_visits
+    }
+    /// Visits of a cell relative to the busiest cell, from 0 to 1
+    pub fn intensity(&self, row: usize, col: usize) -> f32 {
+        match self.max_visits() {
+            0 => 0.0,
+            max => self.visits[row][col] as f32 / max as f32,
+        }
+    }
+}
+
+/// Color of a heatmap cell as RGBA from 0 to 1, white when cold, then yellow, then red
+/// # Example
+/// ```
+/// use word_search_solver::heatmap::heat_color;
+/// assert_eq!(heat_color(0.0), [1.0, 1.0, 1.0, 1.0]);
+/// assert_eq!(heat_color(0.5), [1.0, 1.0, 0.0, 1.0]);
+/// assert_eq!(heat_color(1.0), [1.0, 0.0, 0.0, 1.0]);
+/// ```
+pub fn heat_color(intensity: f32) -> [f32; 4] {
+    let t = intensity.clamp(0.0, 1.0);
+    if t < 0.5 {
+        [1.0, 1.0, 1.0 - 2.0 * t, 1.0]
+    } else {
+        [1.0, 2.0 - 2.0 * t, 0.0, 1.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::Trace;
+
+    #[test]
+    fn test_heatmap_of_whole_search() {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let trie = Trie::from(&vec!["abc"]);
+        let trace = Trace::record(board.clone(), Trie::from(&vec!["abc"]));
+        let heatmap = Heatmap::from_steps(&board, &trie, &trace.steps);
+        // Every cell starts 8 probes, only "a" leads anywhere
+        assert!(heatmap.visits.iter().flatten().all(|visits| *visits >= 8));
+        assert_eq!(heatmap.prunes[0][0], 0);
+        assert_eq!(heatmap.prunes[1][1], 8 + 1);
+        assert_eq!(heatmap.max_visits(), heatmap.visits[0][0]);
+        assert_eq!(heatmap.intensity(0, 0), 1.0);
+
+        let mut cleared = heatmap.clone();
+        cleared.clear();
+        assert_eq!(cleared, Heatmap::new(3, 3));
+    }
+}
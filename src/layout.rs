@@ -0,0 +1,253 @@
+use crate::constant::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Height of an entry of the word list, in pixel
+pub const WORD_LINE_HEIGHT: f32 = 50.0;
+/// Width of a column of the word list, in pixel
+pub const WORD_COLUMN_WIDTH: f32 = 250.0;
+/// Smallest cell `Layout::fit` shrinks the grid to, in pixel
+pub const MIN_GRID_SIZE: f32 = 20.0;
+/// Biggest cell `Layout::fit` grows the grid to, in pixel
+pub const MAX_GRID_SIZE: f32 = 2.0 * GRID_SIZE;
+// Room left under the grid for the timeline and the status line, in pixel
+const BOTTOM_MARGIN: f32 = 80.0;
+
+/// Pixel geometry of a board on screen or on an image.
+///
+/// Every renderer (the ggez visualizer and the headless image exporters) asks the layout
+/// where a cell or a word list entry goes, so the output looks the same everywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Layout {
+    pub start_x: f32,
+    pub start_y: f32,
+    pub grid_size: f32,
+    pub rows: usize,
+    pub cols: usize,
+    /// Entries of the word list per column, the list wraps into another column after that many
+    pub word_rows: usize,
+}
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            start_x: START_X,
+            start_y: START_Y,
+            grid_size: GRID_SIZE,
+            rows: BOARD_SIZE,
+            cols: BOARD_SIZE,
+            word_rows: usize::MAX,
+        }
+    }
+}
+impl Layout {
+    pub fn new(start_x: f32, start_y: f32, grid_size: f32, rows: usize, cols: usize) -> Self {
+        Layout {
+            start_x,
+            start_y,
+            grid_size,
+            rows,
+            cols,
+            word_rows: usize::MAX,
+        }
+    }
+    /// Layout of the `idx`-th of `count` side by side panes sharing a `width` x `height` window.
+    /// The grid is as big as the pane allows, centered horizontally, with room above it for a
+    /// title and below it for a few lines of text.
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::pane(1, 2, 10, 10, 1600.0, 900.0);
+    /// assert_eq!(layout.grid_size, 65.0);
+    /// assert_eq!((layout.start_x, layout.start_y), (875.0, 100.0));
+    /// ```
+    pub fn pane(
+        idx: usize,
+        count: usize,
+        rows: usize,
+        cols: usize,
+        width: f32,
+        height: f32,
+    ) -> Self {
+        let pane_width = width / count as f32;
+        let grid_size = f32::min(
+            (pane_width - 100.0) / cols as f32,
+            (height - 250.0) / rows as f32,
+        );
+        Layout {
+            start_x: pane_width * idx as f32 + (pane_width - grid_size * cols as f32) / 2.0,
+            start_y: 100.0,
+            grid_size,
+            rows,
+            cols,
+            word_rows: usize::MAX,
+        }
+    }
+    /// Layout of the visualizer for a `rows` x `cols` board and `word_count` words in a
+    /// `width` x `height` window.
+    /// The stats panel keeps the space on the left and the word list wraps into as many columns
+    /// as the window height needs. The cells are as big as the rest of the window allows, and
+    /// the grid and the word list are centered horizontally.
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::fit(15, 15, 1600.0, 900.0, 12);
+    /// assert_eq!(layout.grid_size, 48.0);
+    /// assert_eq!(layout.word_rows, 14);
+    /// assert_eq!(layout.start_x, 465.0);
+    /// // Too many words for one column, the list wraps and the grid shrinks to make room
+    /// let layout = Layout::fit(15, 15, 1600.0, 900.0, 20);
+    /// assert_eq!(layout.grid_size, 40.0);
+    /// assert_eq!(layout.word_list_pos(14).1, layout.word_list_pos(0).1);
+    /// assert!(layout.word_list_pos(14).0 > layout.word_list_pos(13).0);
+    /// ```
+    pub fn fit(rows: usize, cols: usize, width: f32, height: f32, word_count: usize) -> Self {
+        let list_height = height - START_Y - BOTTOM_MARGIN;
+        let word_rows = usize::max((list_height / WORD_LINE_HEIGHT) as usize, 1);
+        let word_cols = usize::max(word_count.div_ceil(word_rows), 1);
+        let list_width = 100.0 + WORD_COLUMN_WIDTH * word_cols as f32;
+        let grid_size = f32::min(
+            (width - START_X - list_width) / cols as f32,
+            list_height / rows as f32,
+        )
+        .floor()
+        .clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        let free_width = width - START_X - grid_size * cols as f32 - list_width;
+        Layout {
+            start_x: START_X + f32::max(free_width / 2.0, 0.0),
+            start_y: START_Y,
+            grid_size,
+            rows,
+            cols,
+            word_rows,
+        }
+    }
+    /// Width of the whole grid, in pixel
+    pub fn grid_width(&self) -> f32 {
+        self.grid_size * self.cols as f32
+    }
+    /// Height of the whole grid, in pixel
+    pub fn grid_height(&self) -> f32 {
+        self.grid_size * self.rows as f32
+    }
+    /// Get the top-left corner of a cell
+    /// # Arguments
+    /// * `row` - The row index of the cell
+    /// * `col` - The column index of the cell
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(10.0, 20.0, 50.0, 3, 3);
+    /// assert_eq!(layout.cell_origin(0, 0), (10.0, 20.0));
+    /// assert_eq!(layout.cell_origin(1, 2), (110.0, 70.0));
+    /// ```
+    pub fn cell_origin(&self, row: usize, col: usize) -> (f32, f32) {
+        (
+            self.start_x + self.grid_size * col as f32,
+            self.start_y + self.grid_size * row as f32,
+        )
+    }
+    /// Get the center of a cell, where letters and strike lines are anchored
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(10.0, 20.0, 50.0, 3, 3);
+    /// assert_eq!(layout.cell_center(0, 0), (35.0, 45.0));
+    /// assert_eq!(layout.cell_center(2, 1), (85.0, 145.0));
+    /// ```
+    pub fn cell_center(&self, row: usize, col: usize) -> (f32, f32) {
+        let (x, y) = self.cell_origin(row, col);
+        (x + self.grid_size / 2.0, y + self.grid_size / 2.0)
+    }
+    /// Same as `cell_center`, but from a 1D index of the board
+    pub fn cell_center_1d(&self, index: usize) -> (f32, f32) {
+        self.cell_center(index / self.cols, index % self.cols)
+    }
+    /// Get the (row, column) of the cell under a point, if any
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(100.0, 100.0, 50.0, 3, 3);
+    /// assert_eq!(layout.cell_at(110.0, 160.0), Some((1, 0)));
+    /// assert_eq!(layout.cell_at(90.0, 160.0), None);
+    /// assert_eq!(layout.cell_at(260.0, 160.0), None);
+    /// ```
+    pub fn cell_at(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        if x < self.start_x || y < self.start_y {
+            return None;
+        }
+        let row = ((y - self.start_y) / self.grid_size) as usize;
+        let col = ((x - self.start_x) / self.grid_size) as usize;
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+        Some((row, col))
+    }
+    /// Get the position of the `idx`-th entry of the word list, drawn on the right of the grid
+    /// in columns of `word_rows` entries
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let mut layout = Layout::new(0.0, 0.0, 50.0, 3, 3);
+    /// assert_eq!(layout.word_list_pos(0), (250.0, 0.0));
+    /// assert_eq!(layout.word_list_pos(2), (250.0, 100.0));
+    /// layout.word_rows = 2;
+    /// assert_eq!(layout.word_list_pos(2), (500.0, 0.0));
+    /// ```
+    pub fn word_list_pos(&self, idx: usize) -> (f32, f32) {
+        let (col, row) = (idx / self.word_rows, idx % self.word_rows);
+        (
+            self.start_x + self.grid_width() + 100.0 + WORD_COLUMN_WIDTH * col as f32,
+            self.start_y + WORD_LINE_HEIGHT * row as f32,
+        )
+    }
+    /// Get the index of the word list entry under a point, if any
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let mut layout = Layout::new(0.0, 0.0, 50.0, 3, 3);
+    /// assert_eq!(layout.word_list_index_at(260.0, 10.0), Some(0));
+    /// assert_eq!(layout.word_list_index_at(260.0, 120.0), Some(2));
+    /// assert_eq!(layout.word_list_index_at(100.0, 10.0), None);
+    /// layout.word_rows = 2;
+    /// assert_eq!(layout.word_list_index_at(510.0, 60.0), Some(3));
+    /// assert_eq!(layout.word_list_index_at(260.0, 120.0), None);
+    /// ```
+    pub fn word_list_index_at(&self, x: f32, y: f32) -> Option<usize> {
+        let (list_x, list_y) = self.word_list_pos(0);
+        if x < list_x || y < list_y {
+            return None;
+        }
+        let col = ((x - list_x) / WORD_COLUMN_WIDTH) as usize;
+        let row = ((y - list_y) / WORD_LINE_HEIGHT) as usize;
+        if row >= self.word_rows {
+            return None;
+        }
+        col.checked_mul(self.word_rows)?.checked_add(row)
+    }
+    /// Get the top-left corner of the stats panel, in the free space on the left of the grid
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// assert_eq!(Layout::new(400.0, 100.0, 50.0, 3, 3).stats_panel_pos(), (50.0, 100.0));
+    /// assert_eq!(Layout::new(0.0, 0.0, 50.0, 3, 3).stats_panel_pos(), (0.0, 0.0));
+    /// ```
+    pub fn stats_panel_pos(&self) -> (f32, f32) {
+        (f32::max(self.start_x - 350.0, 0.0), self.start_y)
+    }
+    /// Get the rectangle `(x, y, width, height)` of the replay timeline, under the grid
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(0.0, 0.0, 50.0, 3, 3);
+    /// assert_eq!(layout.timeline_rect(), (0.0, 195.0, 150.0, 20.0));
+    /// ```
+    pub fn timeline_rect(&self) -> (f32, f32, f32, f32) {
+        (
+            self.start_x,
+            self.start_y + self.grid_height() + 45.0,
+            self.grid_width(),
+            20.0,
+        )
+    }
+}
@@ -1,5 +1,25 @@
+pub mod batch;
 pub mod board;
 pub mod constant;
+pub mod editor;
+pub mod game;
+pub mod heatmap;
+pub mod layout;
+pub mod line_scan;
+pub mod matches;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod playback;
+pub mod render;
+pub mod session;
+pub mod solver;
 pub mod state;
+pub mod stats;
+pub mod strategy;
+pub mod theme;
+pub mod trace;
 pub mod trie;
+#[cfg(feature = "tui")]
+pub mod tui;
 pub mod utils;
+pub mod verify;
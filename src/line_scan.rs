@@ -0,0 +1,273 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::matches::Match;
+use crate::strategy::{SearchStrategy, StepEvent};
+use crate::trace::TraceStep;
+use crate::trie::Trie;
+use aho_corasick::AhoCorasick;
+
+/// Solve the board by scanning every row, column and diagonal once, in each of the 8 directions,
+/// with an Aho-Corasick automaton built from the target words.
+/// It finds the same words as `solver::solve`, in the same order, without probing every prefix.
+/// # Arguments
+/// * `board` - The board to search
+/// * `trie` - The trie built from the target words
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::line_scan;
+/// use word_search_solver::solver;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+/// let trie = Trie::from(&vec!["abc", "aei", "bg", "fc"]);
+/// assert_eq!(line_scan::solve(&board, &trie), solver::solve(&board, &trie));
+/// ```
+pub fn solve(board: &Board, trie: &Trie) -> Vec<Match> {
+    LineScanner::new(trie).solve(board)
+}
+
+/// The automaton of a word list, built once and reused for every board searched with it
+pub struct LineScanner {
+    automaton: AhoCorasick,
+    // Word and index in the word list of every pattern of the automaton
+    words: Vec<(String, usize)>,
+}
+impl LineScanner {
+    pub fn new(trie: &Trie) -> Self {
+        // The trie search never checks the empty string, and it would match everywhere
+        let words: Vec<(String, usize)> = trie
+            .words()
+            .into_iter()
+            .filter(|word| !word.is_empty())
+            .filter_map(|word| trie.index_of(&word).map(|index| (word, index)))
+            .collect();
+        LineScanner {
+            automaton: AhoCorasick::new(words.iter().map(|(word, _)| word))
+                .expect("the word list is too large for the automaton"),
+            words,
+        }
+    }
+    /// Find every placement of the words, ordered like the stepping engine finds them:
+    /// by start cell, then direction, then length
+    pub fn solve(&self, board: &Board) -> Vec<Match> {
+        let mut found = Vec::new();
+        for direction in Direction::iterator() {
+            for cells in lines(board, direction) {
+                found.extend(self.scan(board, direction, &cells));
+            }
+        }
+        found.sort();
+        found
+    }
+    /// Find the words on one line of the board, in the order they end on it
+    pub fn scan(
+        &self,
+        board: &Board,
+        direction: &Direction,
+        cells: &[(usize, usize)],
+    ) -> Vec<Match> {
+        let mut text = String::new();
+        // Cell index of every byte of the text, letters may take several bytes
+        let mut cell_of_byte = Vec::new();
+        for (idx, (i, j)) in cells.iter().enumerate() {
+            text.push(board.letters[*i][*j]);
+            cell_of_byte.resize(text.len(), idx);
+        }
+        self.automaton
+            .find_overlapping_iter(&text)
+            .map(|hit| {
+                let (start, end) = (cell_of_byte[hit.start()], cell_of_byte[hit.end() - 1]);
+                let (word, index) = &self.words[hit.pattern().as_usize()];
+                Match {
+                    start: cells[start],
+                    direction: *direction,
+                    length: end - start + 1,
+                    word: word.clone(),
+                    index: *index,
+                    end: cells[end],
+                    cells: cells[start..=end].to_vec(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The line scan as a search strategy: one step scans one whole line of the board, going through
+/// the lines of every direction in the order of `Direction::iterator`.
+/// A line can hold several words, the step reports the first one and `found` has all of them.
+pub struct LineScan {
+    board: Board,
+    scanner: LineScanner,
+    lines: Vec<(Direction, Vec<(usize, usize)>)>,
+    next: usize,
+    found: Vec<Match>,
+}
+impl LineScan {
+    pub fn new(board: Board, trie: Trie) -> Self {
+        let lines = Direction::iterator()
+            .flat_map(|direction| {
+                lines(&board, direction)
+                    .into_iter()
+                    .map(|cells| (*direction, cells))
+            })
+            .collect();
+        LineScan {
+            scanner: LineScanner::new(&trie),
+            board,
+            lines,
+            next: 0,
+            found: Vec::new(),
+        }
+    }
+}
+impl SearchStrategy for LineScan {
+    fn name(&self) -> &'static str {
+        "aho-corasick"
+    }
+    fn board(&self) -> &Board {
+        &self.board
+    }
+    fn step(&mut self) -> StepEvent {
+        let Some((direction, cells)) = self.lines.get(self.next) else {
+            return StepEvent::Done;
+        };
+        self.next += 1;
+        let found = self.scanner.scan(&self.board, direction, cells);
+        let step = TraceStep {
+            position: cells[0],
+            direction: *direction,
+            distance: cells.len() as i32 - 1,
+            feasible: true,
+            found: found.first().map(Match::position),
+        };
+        self.found.extend(found);
+        StepEvent::Probe(step)
+    }
+    fn is_done(&self) -> bool {
+        self.next >= self.lines.len()
+    }
+    fn found(&self) -> &[Match] {
+        &self.found
+    }
+    fn steps(&self) -> usize {
+        self.next
+    }
+    fn probe(&self) -> Option<WordPosition> {
+        let (_, cells) = self.lines.get(self.next)?;
+        Some(WordPosition::new(cells[0], cells[cells.len() - 1]))
+    }
+    fn restart(&mut self) {
+        self.next = 0;
+        self.found.clear();
+    }
+}
+
+/// Get the cells of every line of the board going in the given direction, each from its first cell
+/// # Example
+/// ```
+/// use word_search_solver::board::{Board, Direction};
+/// use word_search_solver::line_scan::lines;
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]);
+/// assert_eq!(lines(&board, &Direction::Left)[0], vec![(0, 2), (0, 1), (0, 0)]);
+/// assert_eq!(
+///     lines(&board, &Direction::UpRight),
+///     vec![vec![(0, 0)], vec![(1, 0), (0, 1)], vec![(1, 1), (0, 2)], vec![(1, 2)]]
+/// );
+/// ```
+pub fn lines(board: &Board, direction: &Direction) -> Vec<Vec<(usize, usize)>> {
+    let mut lines = Vec::new();
+    for i in 0..board.get_rows() {
+        for j in 0..board.get_cols() {
+            // A line starts on the cells with no previous cell in the direction
+            let previous = Board::get_pos_from_direction(i, j, direction, -1);
+            if previous.is_some_and(|(pi, pj)| pi < board.get_rows() && pj < board.get_cols()) {
+                continue;
+            }
+            let mut cells = Vec::new();
+            let mut distance = 0;
+            while let Some((ci, cj)) = Board::get_pos_from_direction(i, j, direction, distance)
+                .filter(|(ci, cj)| *ci < board.get_rows() && *cj < board.get_cols())
+            {
+                cells.push((ci, cj));
+                distance += 1;
+            }
+            lines.push(cells);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    #[test]
+    fn test_same_words_as_the_trie_search() {
+        let square = Board::new(&vec![
+            vec!['a', 'b', 'a'],
+            vec!['b', 'a', 'b'],
+            vec!['a', 'b', 'a'],
+        ]);
+        let wide = Board::new(&vec![
+            vec!['r', 'a', 'c', 'e', 'c', 'a', 'r'],
+            vec!['x', 'é', 't', 'é', 'x', 'y', 'z'],
+        ]);
+        let cases = [
+            // Palindromes, overlapping words and single letters, found once per direction
+            (square, vec!["aba", "ab", "a", "bab", "abab", ""]),
+            (
+                wide,
+                vec!["racecar", "car", "été", "té", "ct", "zy", "missing"],
+            ),
+        ];
+        for (board, words) in cases {
+            let trie = Trie::from(&words);
+            let expected = solver::solve(&board, &trie);
+            assert!(!expected.is_empty());
+            assert_eq!(solve(&board, &trie), expected);
+        }
+    }
+    #[test]
+    fn test_same_words_on_a_generated_board() {
+        // Small alphabet so that words repeat and overlap a lot
+        let mut seed: u32 = 7;
+        let mut next_letter = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            ['a', 'b', 'c', 'd'][(seed >> 16) as usize % 4]
+        };
+        let letters: Vec<Vec<char>> = (0..12)
+            .map(|_| (0..9).map(|_| next_letter()).collect())
+            .collect();
+        let board = Board::new(&letters);
+        let words = vec!["abc", "dab", "cc", "bad", "abcd", "dcba", "aaa", "d"];
+        let trie = Trie::from(&words);
+        let scanner = LineScanner::new(&trie);
+        assert_eq!(scanner.solve(&board), solver::solve(&board, &trie));
+        assert_eq!(lines(&board, &Direction::DownRight).len(), 12 + 9 - 1);
+    }
+    #[test]
+    fn test_stepping_one_line_at_a_time() {
+        let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]);
+        let trie = Trie::from(&vec!["ab", "bc", "fc"]);
+        let mut strategy = LineScan::new(board.clone(), trie.clone());
+        // The first lines go up, from the bottom row
+        assert_eq!(strategy.probe(), Some(WordPosition::new((1, 0), (0, 0))));
+        let mut steps = Vec::new();
+        while let StepEvent::Probe(step) = strategy.step() {
+            steps.push(step);
+        }
+        assert!(strategy.is_done() && strategy.probe().is_none());
+        // "fc" is the only word of its line, "ab" and "bc" share the first row
+        let up = &steps[2];
+        assert_eq!(up.found, Some(WordPosition::new((1, 2), (0, 2))));
+        let row = steps
+            .iter()
+            .find(|step| step.found.is_some() && step.position == (0, 0));
+        assert_eq!(row.unwrap().distance, 2);
+        let mut found = strategy.found().to_vec();
+        found.sort();
+        assert_eq!(found, solver::solve(&board, &trie));
+        strategy.restart();
+        assert_eq!((strategy.steps(), strategy.found().len()), (0, 0));
+    }
+}
@@ -1,10 +1,431 @@
+#[cfg(feature = "ggez")]
 use ggez::conf::WindowMode;
-use ggez::event::{self};
-use ggez::{ContextBuilder, GameResult};
-use std::{env, path};
+#[cfg(feature = "ggez")]
+use ggez::event::EventHandler;
+#[cfg(feature = "ggez")]
+use ggez::{Context, ContextBuilder, GameResult};
+use std::env;
+use std::error::Error;
+use std::fs;
+#[cfg(feature = "ggez")]
+use std::path;
+use std::path::Path;
+#[cfg(feature = "ggez")]
+use std::path::PathBuf;
+#[cfg(feature = "ggez")]
+use word_search_solver::batch::puzzle_at;
+use word_search_solver::batch::{discover, read_manifest, BatchReport};
+use word_search_solver::board::{Board, Direction};
+#[cfg(feature = "ggez")]
 use word_search_solver::constant::*;
-use word_search_solver::state::main_state::MainState;
-fn main() -> GameResult {
+use word_search_solver::render::animation::{
+    render_frames, save_gif, save_png_sequence, AnimationOptions,
+};
+use word_search_solver::render::ansi::render_solution;
+use word_search_solver::render::pdf::{save_pdf, PdfOptions, Sheet};
+use word_search_solver::render::raster::{render_answer_key, render_puzzle};
+#[cfg(feature = "ggez")]
+use word_search_solver::session::Session;
+use word_search_solver::solver::solve;
+#[cfg(feature = "ggez")]
+use word_search_solver::state::compare_state::CompareState;
+#[cfg(feature = "ggez")]
+use word_search_solver::state::event_loop::{self, FileDropHandler};
+#[cfg(feature = "ggez")]
+use word_search_solver::state::main_state::{MainState, SESSION_PATH};
+use word_search_solver::strategy::{by_name, SearchStrategy, STRATEGY_NAMES};
+use word_search_solver::trace::Trace;
+use word_search_solver::trie::Trie;
+use word_search_solver::utils::{read_board, read_target_words};
+use word_search_solver::verify::{verify, AnswerKey};
+
+const USAGE: &str = "Usage:
+  word-search-solver [<puzzle dir|board> | <board> <words>]  run the visualizer
+  word-search-solver play [<puzzle dir|board> | <board> <words>]
+                                                             find the words yourself with the mouse
+  word-search-solver replay <board> <words> <trace>           replay a recorded trace in the visualizer
+  word-search-solver resume [<session>]                      pick a saved visualizer session back up
+  word-search-solver compare <board> <words> [<strategy>...] watch the search strategies side by side
+  word-search-solver trace <board> <words> <output>          record the trace of a whole search
+  word-search-solver tui [--strategy <name>] <board> <words>
+                                                             run the visualizer in the terminal
+  word-search-solver solve [--strategy <name>] <board> <words>
+                                                             print the solved board in the terminal
+                                                             strategies: trie, brute-force, aho-corasick
+  word-search-solver render <board> <words> <output dir>     write puzzle.png and answer.png
+  word-search-solver animate <board> <words> <output.gif|output dir> [--every <n>] [--delay <ms>]
+                                                             export the search as a GIF or PNG frames
+  word-search-solver pdf <output.pdf> [--per-page <n>] [--columns <n>] [--answer-key]
+                         <board> <words> [<board> <words>...]  write printable puzzle sheets
+  word-search-solver batch <puzzle dir|manifest> [--json <output.json>]
+                                                             solve many puzzles and report on them
+  word-search-solver verify <board> <words> <answer key> [--directions <name,name,...>]
+                                                             check an answer key against the board";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => run_visualizer(&[]),
+        Some("play") => play(&args[1..]),
+        Some("replay") => replay(&args[1..]),
+        Some("resume") => resume(&args[1..]),
+        Some("compare") => compare(&args[1..]),
+        Some("trace") => record_trace(&args[1..]),
+        Some("tui") => tui(&args[1..]),
+        Some("solve") => solve_in_terminal(&args[1..]),
+        Some("render") => render(&args[1..]),
+        Some("animate") => animate(&args[1..]),
+        Some("pdf") => pdf(&args[1..]),
+        Some("batch") => batch(&args[1..]),
+        Some("verify") => verify_answer_key(&args[1..]),
+        Some(path) if Path::new(path).exists() => run_visualizer(&args),
+        Some(_) => Err(USAGE.into()),
+    }
+}
+
+// Record every step of the search, to be replayed or compared later
+fn record_trace(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [board_path, words_path, output] = args else {
+        return Err(USAGE.into());
+    };
+    let (board, _, trie) = load(board_path, words_path)?;
+    let trace = Trace::record(board, trie);
+    trace.save(Path::new(output))?;
+    println!("Recorded {} steps to {}", trace.steps.len(), output);
+    Ok(())
+}
+
+// Read a board and its word list, and build the trie of the words
+fn load(board_path: &str, words_path: &str) -> Result<(Board, Vec<String>, Trie), Box<dyn Error>> {
+    let board = read_board(Path::new(board_path))?;
+    let words = read_target_words(Path::new(words_path))?;
+    let trie = Trie::from(&words.iter().map(String::as_str).collect());
+    Ok((board, words, trie))
+}
+
+#[cfg(feature = "tui")]
+fn tui(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (name, args) = split_strategy(args)?;
+    let [board_path, words_path] = &args[..] else {
+        return Err(USAGE.into());
+    };
+    let (board, _, trie) = load(board_path, words_path)?;
+    word_search_solver::tui::run(strategy(&name, board, trie)?)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "tui"))]
+fn tui(_args: &[String]) -> Result<(), Box<dyn Error>> {
+    Err("The terminal visualizer needs the `tui` feature".into())
+}
+
+// Print the board with every found word colorized, set NO_COLOR to get plain text
+fn solve_in_terminal(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (name, args) = split_strategy(args)?;
+    let [board_path, words_path] = &args[..] else {
+        return Err(USAGE.into());
+    };
+    let (board, words, trie) = load(board_path, words_path)?;
+    let mut strategy = strategy(&name, board.clone(), trie)?;
+    strategy.finish();
+    // In the order of the board whatever the strategy, the line scan finds the words line by line
+    let mut found = strategy.found().to_vec();
+    found.sort();
+    let color = env::var_os("NO_COLOR").is_none();
+    print!("{}", render_solution(&board, &words, &found, color));
+    Ok(())
+}
+
+// Take the `--strategy <name>` option out of the arguments, the trie search is the default
+fn split_strategy(args: &[String]) -> Result<(String, Vec<String>), Box<dyn Error>> {
+    let mut name = STRATEGY_NAMES[0].to_string();
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--strategy" => name = args.next().ok_or(USAGE)?.clone(),
+            _ => rest.push(arg.clone()),
+        }
+    }
+    Ok((name, rest))
+}
+
+fn strategy(
+    name: &str,
+    board: Board,
+    trie: Trie,
+) -> Result<Box<dyn SearchStrategy>, Box<dyn Error>> {
+    by_name(name, board, trie).ok_or_else(|| {
+        format!(
+            "Unknown strategy {}, expected one of: {}",
+            name,
+            STRATEGY_NAMES.join(", ")
+        )
+        .into()
+    })
+}
+
+// Render the blank puzzle and its answer key to PNG, without opening a window
+fn render(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [board_path, words_path, output_dir] = args else {
+        return Err(USAGE.into());
+    };
+    let (board, words, trie) = load(board_path, words_path)?;
+    let found = solve(&board, &trie);
+
+    let output_dir = Path::new(output_dir);
+    std::fs::create_dir_all(output_dir)?;
+    render_puzzle(&board, &words).save_png(&output_dir.join("puzzle.png"))?;
+    render_answer_key(&board, &words, &found).save_png(&output_dir.join("answer.png"))?;
+    Ok(())
+}
+
+// Export the search animation, as a GIF when the output ends with .gif and as numbered PNG otherwise
+fn animate(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut options = AnimationOptions::default();
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--every" => options.every = args.next().ok_or(USAGE)?.parse()?,
+            "--delay" => options.delay_ms = args.next().ok_or(USAGE)?.parse()?,
+            _ => paths.push(arg),
+        }
+    }
+    let [board_path, words_path, output] = paths[..] else {
+        return Err(USAGE.into());
+    };
+    let (board, _, trie) = load(board_path, words_path)?;
+    let trace = Trace::record(board.clone(), trie);
+    let frames = render_frames(&board, &trace, &options);
+    let output = Path::new(output);
+    let count = if output.extension().is_some_and(|ext| ext == "gif") {
+        save_gif(frames, options.delay_ms, output)?
+    } else {
+        save_png_sequence(frames, output)?
+    };
+    println!("Wrote {} frames to {}", count, output.display());
+    Ok(())
+}
+
+// Lay out one or more puzzles on printable pages, the title of each sheet is its board file name
+fn pdf(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (output, args) = args.split_first().ok_or(USAGE)?;
+    let mut options = PdfOptions::default();
+    let mut files = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--per-page" => options.puzzles_per_page = args.next().ok_or(USAGE)?.parse()?,
+            "--columns" => options.word_columns = args.next().ok_or(USAGE)?.parse()?,
+            "--answer-key" => options.answer_key = true,
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() || files.len() % 2 != 0 {
+        return Err(USAGE.into());
+    }
+    let puzzles: Vec<(String, Board, Vec<String>)> = files
+        .chunks(2)
+        .map(|pair| {
+            let board_path = Path::new(pair[0]);
+            let title = board_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let board = read_board(board_path)?;
+            Ok((title, board, read_target_words(Path::new(pair[1]))?))
+        })
+        .collect::<Result<_, Box<dyn Error>>>()?;
+    let sheets: Vec<Sheet> = puzzles
+        .iter()
+        .map(|(title, board, words)| Sheet {
+            title: title.clone(),
+            board,
+            words,
+        })
+        .collect();
+    save_pdf(&sheets, &options, Path::new(output))
+}
+
+// Solve every puzzle of a directory or a manifest, print the summary and optionally write the JSON report
+fn batch(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut json = None;
+    let mut inputs = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = Some(args.next().ok_or(USAGE)?),
+            _ => inputs.push(arg),
+        }
+    }
+    let [input] = inputs[..] else {
+        return Err(USAGE.into());
+    };
+    let input = Path::new(input);
+    let puzzles = if input.is_dir() {
+        discover(input)?
+    } else {
+        read_manifest(input)?
+    };
+    if puzzles.is_empty() {
+        return Err(format!("No puzzles found in {}", input.display()).into());
+    }
+    let report = BatchReport::run(&puzzles);
+    print!("{}", report.summary());
+    if let Some(json) = json {
+        fs::write(json, report.to_json())?;
+        println!("Wrote the report to {}", json);
+    }
+    Ok(())
+}
+
+// Check an answer key, fail with the number of problems so that scripts can tell
+fn verify_answer_key(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut directions: Vec<Direction> = Direction::iterator().copied().collect();
+    let mut files = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--directions" => {
+                directions = args
+                    .next()
+                    .ok_or(USAGE)?
+                    .split(',')
+                    .map(|name| Direction::try_from(name.to_string()))
+                    .collect::<Result<_, _>>()?
+            }
+            _ => files.push(arg),
+        }
+    }
+    let [board_path, words_path, key_path] = files[..] else {
+        return Err(USAGE.into());
+    };
+    let (board, words, _) = load(board_path, words_path)?;
+    let key = AnswerKey::load(Path::new(key_path))?;
+    let problems = verify(&board, &words, &key, &directions);
+    for problem in problems.iter() {
+        println!("{}", problem);
+    }
+    if !problems.is_empty() {
+        return Err(format!("{} problems found", problems.len()).into());
+    }
+    println!("{} placements verified", key.placements.len());
+    Ok(())
+}
+
+#[cfg(not(feature = "ggez"))]
+fn run_visualizer(_args: &[String]) -> Result<(), Box<dyn Error>> {
+    Err("The visualizer needs the `ggez` feature".into())
+}
+
+#[cfg(not(feature = "ggez"))]
+fn play(args: &[String]) -> Result<(), Box<dyn Error>> {
+    run_visualizer(args)
+}
+
+#[cfg(not(feature = "ggez"))]
+fn compare(args: &[String]) -> Result<(), Box<dyn Error>> {
+    run_visualizer(args)
+}
+
+#[cfg(not(feature = "ggez"))]
+fn replay(args: &[String]) -> Result<(), Box<dyn Error>> {
+    run_visualizer(args)
+}
+
+#[cfg(not(feature = "ggez"))]
+fn resume(args: &[String]) -> Result<(), Box<dyn Error>> {
+    run_visualizer(args)
+}
+
+// The puzzle given on the command line: a directory or a board file, or a board and its word list
+#[cfg(feature = "ggez")]
+fn puzzle_args(args: &[String]) -> Result<Option<(PathBuf, PathBuf)>, Box<dyn Error>> {
+    match args {
+        [] => Ok(None),
+        [path] => {
+            let puzzle = puzzle_at(Path::new(path))?;
+            Ok(Some((puzzle.board, puzzle.words)))
+        }
+        [board_path, words_path] => Ok(Some((board_path.into(), words_path.into()))),
+        _ => Err(USAGE.into()),
+    }
+}
+
+#[cfg(feature = "ggez")]
+fn run_visualizer(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let puzzle = puzzle_args(args)?;
+    open_window(move |ctx| match &puzzle {
+        Some((board_path, words_path)) => MainState::with_puzzle(ctx, board_path, words_path),
+        None => MainState::new(ctx),
+    })
+}
+
+#[cfg(feature = "ggez")]
+fn play(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let puzzle = puzzle_args(args)?;
+    open_window(move |ctx| {
+        let mut state = match &puzzle {
+            Some((board_path, words_path)) => MainState::with_puzzle(ctx, board_path, words_path)?,
+            None => MainState::new(ctx)?,
+        };
+        state.start_game();
+        Ok(state)
+    })
+}
+
+#[cfg(feature = "ggez")]
+fn compare(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [board_path, words_path, names @ ..] = args else {
+        return Err(USAGE.into());
+    };
+    let names: Vec<&str> = if names.is_empty() {
+        STRATEGY_NAMES.to_vec()
+    } else {
+        names.iter().map(String::as_str).collect()
+    };
+    let (board, _, trie) = load(board_path, words_path)?;
+    let strategies = names
+        .iter()
+        .map(|name| strategy(name, board.clone(), trie.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    open_window(move |ctx| CompareState::new(ctx, strategies))
+}
+
+#[cfg(feature = "ggez")]
+fn replay(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [board_path, words_path, trace_path] = args else {
+        return Err(USAGE.into());
+    };
+    let trace = Trace::load(Path::new(trace_path))?;
+    let (board_path, words_path) = (board_path.clone(), words_path.clone());
+    open_window(move |ctx| {
+        let mut state =
+            MainState::with_puzzle(ctx, Path::new(&board_path), Path::new(&words_path))?;
+        state
+            .start_replay(trace)
+            .map_err(ggez::GameError::CustomError)?;
+        Ok(state)
+    })
+}
+
+// The session saved when the visualizer was last closed is used when no path is given
+#[cfg(feature = "ggez")]
+fn resume(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let path = match args {
+        [] => SESSION_PATH,
+        [path] => path.as_str(),
+        _ => return Err(USAGE.into()),
+    };
+    let session = Session::load(Path::new(path))?;
+    open_window(move |ctx| MainState::from_session(ctx, session))
+}
+
+#[cfg(feature = "ggez")]
+fn open_window<S: EventHandler + FileDropHandler + 'static>(
+    setup: impl FnOnce(&mut Context) -> GameResult<S>,
+) -> Result<(), Box<dyn Error>> {
     // Make a Context.
     let resource_dir = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
         let mut path = path::PathBuf::from(manifest_dir);
@@ -22,11 +443,12 @@ fn main() -> GameResult {
     // Create an instance of your event handler.
     // Usually, you should provide it with the Context object to
     // use when setting your game up.
-    let state = MainState::new(&mut ctx)?;
+    let state = setup(&mut ctx)?;
 
     // Run!
-    event::run(ctx, event_loop, state);
+    event_loop::run(ctx, event_loop, state);
 }
+#[cfg(feature = "ggez")]
 pub fn window_state_mode() -> WindowMode {
     WindowMode {
         width: SCREEN_WIDTH,
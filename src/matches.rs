@@ -0,0 +1,125 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A target word found on the board.
+/// Matches sort in the order the search finds them: by start cell, then direction, then length.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Match {
+    /// The (row, column) of the first letter
+    pub start: (usize, usize),
+    pub direction: Direction,
+    /// Number of letters
+    pub length: usize,
+    pub word: String,
+    /// Position of the word in the word list
+    pub index: usize,
+    /// The (row, column) of the last letter
+    pub end: (usize, usize),
+    /// Every cell of the word, from start to end
+    pub cells: Vec<(usize, usize)>,
+}
+impl Match {
+    /// Read the line of `distance + 1` letters from `start` in `direction`, and make a match of it
+    /// if it spells a word of the trie
+    /// # Arguments
+    /// * `board` - The board to read
+    /// * `trie` - The trie built from the word list
+    /// * `start` - The (row, column) of the first letter
+    /// * `direction` - The direction to read in
+    /// * `distance` - The distance from the first letter to the last one
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Board, Direction};
+    /// use word_search_solver::matches::Match;
+    /// use word_search_solver::trie::Trie;
+    /// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// let trie = Trie::from(&vec!["abc", "fc"]);
+    /// let fc = Match::on_board(&board, &trie, (1, 2), Direction::Up, 1).unwrap();
+    /// assert_eq!(fc.word, "fc");
+    /// assert_eq!(fc.index, 1);
+    /// assert_eq!(fc.end, (0, 2));
+    /// assert_eq!(fc.cells, vec![(1, 2), (0, 2)]);
+    /// assert_eq!(fc.to_string(), "fc (1, 2) -> (0, 2) up");
+    /// assert_eq!(Match::on_board(&board, &trie, (1, 2), Direction::Up, 0), None);
+    /// assert_eq!(Match::on_board(&board, &trie, (0, 0), Direction::Up, 1), None);
+    /// ```
+    pub fn on_board(
+        board: &Board,
+        trie: &Trie,
+        start: (usize, usize),
+        direction: Direction,
+        distance: i32,
+    ) -> Option<Match> {
+        let word = board.get_string_from_direction(start.0, start.1, &direction, distance)?;
+        let index = trie.index_of(&word)?;
+        let cells: Vec<(usize, usize)> = (0..=distance)
+            .map(|k| Board::get_pos_from_direction(start.0, start.1, &direction, k))
+            .collect::<Option<_>>()?;
+        Some(Match {
+            start,
+            direction,
+            length: cells.len(),
+            word,
+            index,
+            end: *cells.last()?,
+            cells,
+        })
+    }
+    /// The start and end cells, for the code drawing lines between them
+    pub fn position(&self) -> WordPosition {
+        WordPosition::new(self.start, self.end)
+    }
+}
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} -> {:?} {}",
+            self.word,
+            self.start,
+            self.end,
+            self.direction.name()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::solve;
+
+    #[test]
+    fn test_matches_sort_in_search_order() {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let trie = Trie::from(&vec!["hd", "ihg", "abc", "aei", "a", "ie"]);
+        let found = solve(&board, &trie);
+        let mut sorted = found.clone();
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, found);
+        // A single letter is found once per direction
+        assert_eq!(found.iter().filter(|m| m.word == "a").count(), 8);
+        let words: Vec<&str> = found
+            .iter()
+            .map(|m| m.word.as_str())
+            .filter(|word| *word != "a")
+            .collect();
+        assert_eq!(words, ["abc", "aei", "hd", "ihg", "ie"]);
+        let ie = found.last().unwrap();
+        assert_eq!(
+            (ie.index, ie.direction, ie.length),
+            (5, Direction::UpLeft, 2)
+        );
+        assert_eq!(ie.position(), WordPosition::new((2, 2), (1, 1)));
+        let unique: std::collections::HashSet<&Match> = found.iter().collect();
+        assert_eq!(unique.len(), found.len());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::board::Board;
+use crate::matches::Match;
+use crate::solver::{self, solve_from};
+use crate::trie::Trie;
+use rayon::prelude::*;
+
+/// Solve one board with the start cells spread across threads sharing the trie.
+/// The words come back in the same order as `solver::solve`, whatever the number of threads.
+/// # Arguments
+/// * `board` - The board to search
+/// * `trie` - The trie built from the target words
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::{parallel, solver};
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+/// let trie = Trie::from(&vec!["abc", "aei", "fc", "hd"]);
+/// assert_eq!(parallel::solve(&board, &trie), solver::solve(&board, &trie));
+/// ```
+pub fn solve(board: &Board, trie: &Trie) -> Vec<Match> {
+    let cells = board.get_rows() * board.get_cols();
+    (0..cells)
+        .into_par_iter()
+        .map(|idx| solve_from(board, trie, board.get_2d_position(idx)))
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Solve many puzzles, one puzzle per thread at a time
+/// # Returns
+/// * `Vec<Vec<Match>>` - The words found in each puzzle, in the order of the puzzles
+pub fn solve_batch(puzzles: &[(Board, Trie)]) -> Vec<Vec<Match>> {
+    puzzles
+        .par_iter()
+        .map(|(board, trie)| solver::solve(board, trie))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_results_match_the_sequential_solver() {
+        let puzzles: Vec<(Board, Trie)> = (0..16)
+            .map(|shift| {
+                let letters: Vec<Vec<char>> = (0..7)
+                    .map(|i| {
+                        (0..5)
+                            .map(|j| (b'a' + ((i * 3 + j * 5 + shift) % 4) as u8) as char)
+                            .collect()
+                    })
+                    .collect();
+                let trie = Trie::from(&vec!["ab", "bad", "cab", "dd", "abcd", "c"]);
+                (Board::new(&letters), trie)
+            })
+            .collect();
+        let expected: Vec<Vec<Match>> = puzzles
+            .iter()
+            .map(|(board, trie)| solver::solve(board, trie))
+            .collect();
+        assert!(expected.iter().all(|found| !found.is_empty()));
+        assert_eq!(solve_batch(&puzzles), expected);
+        for ((board, trie), found) in puzzles.iter().zip(&expected) {
+            assert_eq!(&solve(board, trie), found);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const DEFAULT_STEPS_PER_SECOND: u32 = 30;
+pub const MIN_STEPS_PER_SECOND: u32 = 1;
+pub const MAX_STEPS_PER_SECOND: u32 = 960;
+
+/// Pause and speed of an animated search, shared by the front ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Playback {
+    pub paused: bool,
+    pub steps_per_second: u32,
+}
+impl Default for Playback {
+    fn default() -> Self {
+        Playback {
+            paused: false,
+            steps_per_second: DEFAULT_STEPS_PER_SECOND,
+        }
+    }
+}
+impl Playback {
+    pub fn new() -> Self {
+        Playback::default()
+    }
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+    /// Double the speed, up to `MAX_STEPS_PER_SECOND`
+    /// # Example
+    /// ```
+    /// use word_search_solver::playback::{Playback, MAX_STEPS_PER_SECOND};
+    /// let mut playback = Playback::new();
+    /// playback.faster();
+    /// assert_eq!(playback.steps_per_second, 60);
+    /// for _ in 0..10 {
+    ///     playback.faster();
+    /// }
+    /// assert_eq!(playback.steps_per_second, MAX_STEPS_PER_SECOND);
+    /// ```
+    pub fn faster(&mut self) {
+        self.steps_per_second = u32::min(self.steps_per_second * 2, MAX_STEPS_PER_SECOND);
+    }
+    /// Halve the speed, down to `MIN_STEPS_PER_SECOND`
+    /// # Example
+    /// ```
+    /// use word_search_solver::playback::Playback;
+    /// let mut playback = Playback::new();
+    /// playback.slower();
+    /// assert_eq!(playback.steps_per_second, 15);
+    /// for _ in 0..10 {
+    ///     playback.slower();
+    /// }
+    /// assert_eq!(playback.steps_per_second, 1);
+    /// ```
+    pub fn slower(&mut self) {
+        self.steps_per_second = u32::max(self.steps_per_second / 2, MIN_STEPS_PER_SECOND);
+    }
+    /// Time between two steps at the current speed
+    pub fn step_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.steps_per_second
+    }
+}
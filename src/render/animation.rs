@@ -0,0 +1,220 @@
+use crate::board::Board;
+use crate::render::raster::Frame;
+use crate::trace::{Trace, TraceStep};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::Path;
+
+/// How a recorded search is turned into frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnimationOptions {
+    /// Keep one step out of `every`, 1 keeps them all
+    pub every: usize,
+    /// Time each frame stays on screen in the GIF, in milliseconds
+    pub delay_ms: u32,
+}
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions {
+            every: 1,
+            delay_ms: 50,
+        }
+    }
+}
+
+/// Render the frames of a recorded search, like the ggez visualizer shows them:
+/// the found words struck through and listed on the right, and the probe line of the current step.
+/// The last frame shows the finished search, without a probe.
+/// Frames are rendered one at a time as the iterator is consumed, a long search would not fit
+/// in memory all at once.
+/// # Arguments
+/// * `board` - The board the trace was recorded on
+/// * `trace` - The recorded search
+/// * `options` - The frame skipping, `every` is treated as 1 when it is 0
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::render::animation::{render_frames, AnimationOptions};
+/// use word_search_solver::trace::Trace;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+/// let trace = Trace::record(board.clone(), Trie::from(&vec!["ab"]));
+/// let options = AnimationOptions { every: 10, ..Default::default() };
+/// let frames = render_frames(&board, &trace, &options);
+/// assert_eq!(frames.count(), (trace.steps.len() + 9) / 10 + 1);
+/// ```
+pub fn render_frames<'a>(
+    board: &'a Board,
+    trace: &'a Trace,
+    options: &AnimationOptions,
+) -> Frames<'a> {
+    let word_count = trace.found_until(trace.steps.len()).len();
+    let mut persistent = Frame::new(board, word_count);
+    persistent.draw_grid();
+    persistent.draw_letters(board);
+    Frames {
+        board,
+        steps: &trace.steps,
+        every: usize::max(options.every, 1),
+        next_step: 0,
+        found: 0,
+        persistent: Some(persistent),
+    }
+}
+
+/// The frames of `render_frames`, rendered on demand
+pub struct Frames<'a> {
+    board: &'a Board,
+    steps: &'a [TraceStep],
+    every: usize,
+    next_step: usize,
+    found: usize,
+    // Strikes and found words never go away, so they are drawn once on this frame.
+    // It is the last frame, and None once it was returned.
+    persistent: Option<Frame>,
+}
+impl Iterator for Frames<'_> {
+    type Item = Frame;
+    fn next(&mut self) -> Option<Frame> {
+        while let Some(step) = self.steps.get(self.next_step) {
+            let persistent = self.persistent.as_mut()?;
+            let idx = self.next_step;
+            self.next_step += 1;
+            let frame = idx.is_multiple_of(self.every).then(|| {
+                let mut frame = persistent.clone();
+                // Pruned probes are not drawn, like in the visualizer
+                let (i, j) = step.position;
+                let end = Board::get_pos_from_direction(i, j, &step.direction, step.distance);
+                if let (true, Some(end)) = (step.feasible, end) {
+                    frame.draw_probe(step.position, end);
+                }
+                frame
+            });
+            if let Some(word_position) = step.found {
+                persistent.draw_strike(word_position.start(), word_position.end());
+                let (start, end) = word_position.to_1d(self.board.get_cols());
+                let word = self.board.get_word_from_1d_position(start, end);
+                persistent.draw_word(&word, self.found);
+                self.found += 1;
+            }
+            if frame.is_some() {
+                return frame;
+            }
+        }
+        self.persistent.take()
+    }
+}
+
+/// Encode frames into a GIF that loops forever, and return how many were written
+pub fn save_gif(
+    frames: impl IntoIterator<Item = Frame>,
+    delay_ms: u32,
+    path: &Path,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut frames = frames.into_iter().peekable();
+    let Some(first) = frames.peek() else {
+        return Err("Cannot make a GIF without frames".into());
+    };
+    let (width, height) = (first.pixmap.width() as u16, first.pixmap.height() as u16);
+    let mut encoder = gif::Encoder::new(File::create(path)?, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    let mut count = 0;
+    for frame in frames {
+        // The frames are opaque, so the premultiplied pixels are plain RGBA
+        let mut pixels = frame.pixmap.take();
+        let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+        // GIF delays are in hundredths of a second
+        gif_frame.delay = (delay_ms / 10) as u16;
+        encoder.write_frame(&gif_frame)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Write frames as `frame_00000.png`, `frame_00001.png`... in a directory, created if needed,
+/// and return how many were written
+pub fn save_png_sequence(
+    frames: impl IntoIterator<Item = Frame>,
+    dir: &Path,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+    let mut count = 0;
+    for frame in frames {
+        frame.save_png(&dir.join(format!("frame_{:05}.png", count)))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Direction;
+    use crate::trie::Trie;
+
+    fn board() -> Board {
+        Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ])
+    }
+    fn rgb(frame: &Frame, (x, y): (f32, f32)) -> (u8, u8, u8) {
+        let pixel = frame.pixmap.pixel(x as u32, y as u32).unwrap();
+        (pixel.red(), pixel.green(), pixel.blue())
+    }
+    #[test]
+    fn test_frames_show_probe_then_strikes() {
+        let b = board();
+        let trace = Trace::record(b.clone(), Trie::from(&vec!["abc"]));
+        let frames: Vec<Frame> = render_frames(&b, &trace, &AnimationOptions::default()).collect();
+        assert_eq!(frames.len(), trace.steps.len() + 1);
+
+        // Between a and b: green while "ab" is probed, blue once "abc" is found
+        let (x0, y0) = frames[0].layout.cell_center(0, 0);
+        let (x1, _) = frames[0].layout.cell_center(0, 1);
+        let middle = ((x0 + x1) / 2.0, y0);
+        let probe = trace
+            .steps
+            .iter()
+            .position(|step| step.direction == Direction::Right && step.distance == 1)
+            .unwrap();
+        assert_eq!(rgb(&frames[probe], middle), (0, 255, 0));
+        // No word goes on after "abc", so its probe is pruned and not drawn
+        let found = trace
+            .steps
+            .iter()
+            .position(|step| step.found.is_some())
+            .unwrap();
+        assert!(!trace.steps[found].feasible);
+        assert_eq!(rgb(&frames[found], middle), (255, 255, 255));
+        assert_eq!(rgb(frames.last().unwrap(), middle), (0, 0, 255));
+        assert_eq!(rgb(&frames[0], middle), (255, 255, 255));
+    }
+    #[test]
+    fn test_save_gif_and_png_sequence() {
+        let b = board();
+        let trace = Trace::record(b.clone(), Trie::from(&vec!["abc", "aei"]));
+        let options = AnimationOptions {
+            every: 20,
+            delay_ms: 100,
+        };
+        let count = render_frames(&b, &trace, &options).count();
+        let dir = std::env::temp_dir().join("word_search_animation_test");
+        let gif_path = dir.with_extension("gif");
+        let frames = render_frames(&b, &trace, &options);
+        assert_eq!(
+            save_gif(frames, options.delay_ms, &gif_path).unwrap(),
+            count
+        );
+        assert!(fs::read(&gif_path).unwrap().starts_with(b"GIF89a"));
+        let frames = render_frames(&b, &trace, &options);
+        assert_eq!(save_png_sequence(frames, &dir).unwrap(), count);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), count);
+        assert!(dir.join("frame_00000.png").exists());
+        fs::remove_file(&gif_path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+use crate::board::Board;
+use crate::matches::Match;
+use crate::solver::missing_words;
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+// Bold foreground colors, cycled through when there are more words than colors
+const PALETTE: [&str; 12] = [
+    "\x1b[1;31m",
+    "\x1b[1;32m",
+    "\x1b[1;33m",
+    "\x1b[1;34m",
+    "\x1b[1;35m",
+    "\x1b[1;36m",
+    "\x1b[1;91m",
+    "\x1b[1;92m",
+    "\x1b[1;93m",
+    "\x1b[1;94m",
+    "\x1b[1;95m",
+    "\x1b[1;96m",
+];
+
+/// Get the escape sequence used for the `idx`-th found word
+pub fn word_color(idx: usize) -> &'static str {
+    PALETTE[idx % PALETTE.len()]
+}
+
+/// Render the letters of the board, one row per line
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::render::ansi::render_board;
+/// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+/// assert_eq!(render_board(&board), "a b\nc d\n");
+/// ```
+pub fn render_board(board: &Board) -> String {
+    let mut result = String::new();
+    for row in board.letters.iter() {
+        let line: Vec<String> = row.iter().map(char::to_string).collect();
+        result.push_str(&line.join(" "));
+        result.push('\n');
+    }
+    result
+}
+
+/// Render the board with every found word in its own color and the unused cells dimmed,
+/// followed by a legend and the words that were not found.
+/// Without `color`, unused cells are replaced by `.` so the output stays readable in plain text logs.
+/// # Arguments
+/// * `board` - The board
+/// * `words` - The target words
+/// * `found` - The words found by the solver
+/// * `color` - Whether to use ANSI escape sequences
+pub fn render_solution(board: &Board, words: &[String], found: &[Match], color: bool) -> String {
+    // Index of the word covering each cell, the last word wins when words cross
+    let mut owners = vec![vec![None; board.get_cols()]; board.get_rows()];
+    for (idx, word_match) in found.iter().enumerate() {
+        for (i, j) in word_match.cells.iter() {
+            owners[*i][*j] = Some(idx);
+        }
+    }
+
+    let mut result = String::new();
+    for (i, row) in board.letters.iter().enumerate() {
+        let line: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(j, letter)| match (owners[i][j], color) {
+                (Some(idx), true) => format!("{}{}{}", word_color(idx), letter, RESET),
+                (None, true) => format!("{}{}{}", DIM, letter, RESET),
+                (Some(_), false) => letter.to_string(),
+                (None, false) => ".".to_string(),
+            })
+            .collect();
+        result.push_str(&line.join(" "));
+        result.push('\n');
+    }
+
+    result.push('\n');
+    for (idx, word_match) in found.iter().enumerate() {
+        let (word, first, last) = (&word_match.word, word_match.start, word_match.end);
+        if color {
+            result.push_str(&format!(
+                "{}{}{} {:?} -> {:?}\n",
+                word_color(idx),
+                word,
+                RESET,
+                first,
+                last
+            ));
+        } else {
+            result.push_str(&format!("{} {:?} -> {:?}\n", word, first, last));
+        }
+    }
+    let missing = missing_words(words, found);
+    if !missing.is_empty() {
+        result.push_str(&format!("Not found: {}\n", missing.join(", ")));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::solve;
+    use crate::trie::Trie;
+
+    fn board() -> Board {
+        Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ])
+    }
+    #[test]
+    fn test_render_solution_plain() {
+        let b = board();
+        let words: Vec<String> = ["abc", "aei", "xyz"].map(String::from).to_vec();
+        let found = solve(&b, &Trie::from(&vec!["abc", "aei", "xyz"]));
+        assert_eq!(
+            render_solution(&b, &words, &found, false),
+            "a b c\n. e .\n. . i\n\nabc (0, 0) -> (0, 2)\naei (0, 0) -> (2, 2)\nNot found: xyz\n"
+        );
+    }
+    #[test]
+    fn test_render_solution_colors() {
+        let b = board();
+        let words: Vec<String> = ["abc", "cfi"].map(String::from).to_vec();
+        let found = solve(&b, &Trie::from(&vec!["abc", "cfi"]));
+        let output = render_solution(&b, &words, &found, true);
+        let first_row = output.lines().next().unwrap();
+        assert_eq!(
+            first_row,
+            format!(
+                "{0}a{2} {0}b{2} {1}c{2}",
+                word_color(0),
+                word_color(1),
+                RESET
+            )
+        );
+        let second_row = output.lines().nth(1).unwrap();
+        assert!(second_row.starts_with(&format!("{}d{}", DIM, RESET)));
+        assert!(output.contains(&format!("{}cfi{}", word_color(1), RESET)));
+        assert!(!output.contains("Not found"));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod animation;
+pub mod ansi;
+pub mod pdf;
+pub mod raster;
+
+/// Font of the visualizer, bundled so the headless renderers do not depend on the resources folder
+pub(crate) const FONT_DATA: &[u8] = include_bytes!("../../resources/Montserrat-Bold.ttf");
@@ -0,0 +1,313 @@
+use crate::board::Board;
+use crate::layout::Layout;
+use crate::render::FONT_DATA;
+use crate::solver::solve;
+use crate::trie::Trie;
+use ab_glyph::{Font, FontRef};
+use printpdf::{
+    Color, IndirectFontRef, Line, LineCapStyle, Mm, PdfDocument, PdfLayerReference, Point, Rgb,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::io::Cursor;
+use std::path::Path;
+
+// A4 portrait, every length in this module is in millimeter
+pub const PAGE_WIDTH: f32 = 210.0;
+pub const PAGE_HEIGHT: f32 = 297.0;
+pub const PAGE_MARGIN: f32 = 15.0;
+const MAX_CELL_SIZE: f32 = 12.0;
+const TITLE_HEIGHT: f32 = 14.0;
+const TITLE_FONT_SIZE: f32 = 18.0; // in point
+const WORD_FONT_SIZE: f32 = 11.0; // in point
+const WORD_LINE_HEIGHT: f32 = 6.0;
+const GAP: f32 = 6.0;
+const PT_TO_MM: f32 = 0.352_778;
+// Height of a capital letter relative to the font size, used to center letters vertically
+const CAP_HEIGHT: f32 = 0.7;
+
+/// One puzzle to print: a title, the board and the words to find
+pub struct Sheet<'a> {
+    pub title: String,
+    pub board: &'a Board,
+    pub words: &'a [String],
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PdfOptions {
+    /// Number of puzzles stacked vertically on each page
+    pub puzzles_per_page: usize,
+    /// Number of columns used for the word list under each grid
+    pub word_columns: usize,
+    /// Append answer key pages, with every placement highlighted
+    pub answer_key: bool,
+}
+impl Default for PdfOptions {
+    fn default() -> Self {
+        PdfOptions {
+            puzzles_per_page: 1,
+            word_columns: 3,
+            answer_key: false,
+        }
+    }
+}
+
+fn word_list_rows(sheet: &Sheet, options: &PdfOptions) -> usize {
+    sheet.words.len().div_ceil(options.word_columns.max(1))
+}
+
+/// Get the layout of the grid of a sheet, in millimeter from the top-left corner of the page
+/// # Arguments
+/// * `sheet` - The puzzle to lay out
+/// * `slot` - The position of the puzzle on its page, 0 is the top one
+/// * `options` - The export options
+pub fn sheet_layout(sheet: &Sheet, slot: usize, options: &PdfOptions) -> Layout {
+    let rows = sheet.board.get_rows();
+    let cols = sheet.board.get_cols();
+    let slot_height = (PAGE_HEIGHT - 2.0 * PAGE_MARGIN) / options.puzzles_per_page.max(1) as f32;
+    let slot_top = PAGE_MARGIN + slot_height * slot as f32;
+    let list_height = word_list_rows(sheet, options) as f32 * WORD_LINE_HEIGHT;
+    let grid_height = slot_height - TITLE_HEIGHT - GAP - list_height - GAP;
+    let grid_width = PAGE_WIDTH - 2.0 * PAGE_MARGIN;
+    let cell_size =
+        f32::min(grid_width / cols as f32, grid_height / rows as f32).clamp(1.0, MAX_CELL_SIZE);
+    // Center the grid horizontally
+    let start_x = (PAGE_WIDTH - cell_size * cols as f32) / 2.0;
+    Layout::new(start_x, slot_top + TITLE_HEIGHT, cell_size, rows, cols)
+}
+
+/// Get the baseline position of the `idx`-th word, the list is filled column by column under the grid
+pub fn word_position(
+    sheet: &Sheet,
+    layout: &Layout,
+    idx: usize,
+    options: &PdfOptions,
+) -> (f32, f32) {
+    let rows = word_list_rows(sheet, options).max(1);
+    let column_width = (PAGE_WIDTH - 2.0 * PAGE_MARGIN) / options.word_columns.max(1) as f32;
+    (
+        PAGE_MARGIN + column_width * (idx / rows) as f32,
+        layout.start_y + layout.grid_height() + GAP + WORD_LINE_HEIGHT * (idx % rows + 1) as f32,
+    )
+}
+
+// Convert from top-left based millimeters to the bottom-left based PDF coordinates
+fn point(x: f32, y: f32) -> Point {
+    Point::new(Mm(x), Mm(PAGE_HEIGHT - y))
+}
+
+fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+// Width of a text, in millimeter
+fn text_width(metrics: &FontRef, text: &str, font_size: f32) -> f32 {
+    let units_per_em = metrics.units_per_em().unwrap_or(1000.0);
+    let width: f32 = text
+        .chars()
+        .map(|c| metrics.h_advance_unscaled(metrics.glyph_id(c)))
+        .sum();
+    width / units_per_em * font_size * PT_TO_MM
+}
+
+struct Writer<'a> {
+    layer: PdfLayerReference,
+    font: &'a IndirectFontRef,
+    metrics: &'a FontRef<'a>,
+}
+impl Writer<'_> {
+    fn text(&self, text: &str, font_size: f32, x: f32, y: f32) {
+        self.layer
+            .use_text(text, font_size, Mm(x), Mm(PAGE_HEIGHT - y), self.font);
+    }
+    fn line(&self, points: &[(f32, f32)], closed: bool) {
+        self.layer.add_line(Line {
+            points: points.iter().map(|&(x, y)| (point(x, y), false)).collect(),
+            is_closed: closed,
+        });
+    }
+    fn sheet(&self, sheet: &Sheet, slot: usize, options: &PdfOptions, answer_key: bool) {
+        let layout = sheet_layout(sheet, slot, options);
+        let title = if answer_key {
+            format!("{} - Answer key", sheet.title)
+        } else {
+            sheet.title.clone()
+        };
+        self.text(
+            &title,
+            TITLE_FONT_SIZE,
+            layout.start_x,
+            layout.start_y - TITLE_HEIGHT / 2.0,
+        );
+
+        // Highlights go first so the letters stay readable on top of them
+        if answer_key {
+            let trie = Trie::from(&sheet.words.iter().map(String::as_str).collect());
+            self.layer.set_outline_color(rgb(1.0, 0.85, 0.3));
+            self.layer
+                .set_outline_thickness(layout.grid_size * 0.7 / PT_TO_MM);
+            self.layer.set_line_cap_style(LineCapStyle::Round);
+            for word_match in solve(sheet.board, &trie) {
+                let (start, end) = (word_match.start, word_match.end);
+                let start = layout.cell_center(start.0, start.1);
+                let end = layout.cell_center(end.0, end.1);
+                self.line(&[start, end], false);
+            }
+            self.layer.set_line_cap_style(LineCapStyle::Butt);
+        }
+
+        // Grid border
+        let (left, top) = layout.cell_origin(0, 0);
+        let (right, bottom) = (left + layout.grid_width(), top + layout.grid_height());
+        self.layer.set_outline_color(rgb(0.0, 0.0, 0.0));
+        self.layer.set_outline_thickness(1.0);
+        self.line(
+            &[(left, top), (right, top), (right, bottom), (left, bottom)],
+            true,
+        );
+
+        // Letters, centered in their cell
+        let letter_size = layout.grid_size * 0.6 / PT_TO_MM;
+        for (i, row) in sheet.board.letters.iter().enumerate() {
+            for (j, letter) in row.iter().enumerate() {
+                let letter = letter.to_string();
+                let (x, y) = layout.cell_center(i, j);
+                let width = text_width(self.metrics, &letter, letter_size);
+                let cap_height = letter_size * CAP_HEIGHT * PT_TO_MM;
+                self.text(&letter, letter_size, x - width / 2.0, y + cap_height / 2.0);
+            }
+        }
+
+        for (idx, word) in sheet.words.iter().enumerate() {
+            let (x, y) = word_position(sheet, &layout, idx, options);
+            self.text(word, WORD_FONT_SIZE, x, y);
+        }
+    }
+}
+
+/// Lay out the sheets on A4 pages and return the PDF file content
+/// # Arguments
+/// * `sheets` - The puzzles to print, in order
+/// * `options` - The export options
+pub fn render_pdf(sheets: &[Sheet], options: &PdfOptions) -> Result<Vec<u8>, Box<dyn Error>> {
+    if sheets.is_empty() {
+        return Err("No puzzle to export".into());
+    }
+    let per_page = options.puzzles_per_page.max(1);
+    let mut pages: Vec<(&[Sheet], bool)> = sheets.chunks(per_page).map(|c| (c, false)).collect();
+    if options.answer_key {
+        pages.extend(sheets.chunks(per_page).map(|c| (c, true)));
+    }
+
+    let (doc, first_page, first_layer) = PdfDocument::new(
+        sheets[0].title.as_str(),
+        Mm(PAGE_WIDTH),
+        Mm(PAGE_HEIGHT),
+        "Layer 1",
+    );
+    let font = doc.add_external_font(Cursor::new(FONT_DATA))?;
+    let metrics = FontRef::try_from_slice(FONT_DATA)?;
+    for (page_idx, (page_sheets, answer_key)) in pages.into_iter().enumerate() {
+        let (page, layer) = if page_idx == 0 {
+            (first_page, first_layer)
+        } else {
+            doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1")
+        };
+        let writer = Writer {
+            layer: doc.get_page(page).get_layer(layer),
+            font: &font,
+            metrics: &metrics,
+        };
+        for (slot, sheet) in page_sheets.iter().enumerate() {
+            writer.sheet(sheet, slot, options, answer_key);
+        }
+    }
+    Ok(doc.save_to_bytes()?)
+}
+
+/// Same as `render_pdf`, but write the result to a file
+pub fn save_pdf(sheets: &[Sheet], options: &PdfOptions, path: &Path) -> Result<(), Box<dyn Error>> {
+    std::fs::write(path, render_pdf(sheets, options)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{fetch_board, fetch_target_words};
+
+    fn count_pages(pdf: &[u8]) -> usize {
+        let pdf = String::from_utf8_lossy(pdf);
+        pdf.matches("/Type /Page\n").count() + pdf.matches("/Type/Page/").count()
+    }
+    #[test]
+    fn test_sheet_layout_fits_in_its_slot() {
+        let board = Board::new(&fetch_board(Path::new("src/input/board.txt")));
+        let words = fetch_target_words(Path::new("src/input/words.txt"));
+        let sheet = Sheet {
+            title: "Languages".to_string(),
+            board: &board,
+            words: &words,
+        };
+        for puzzles_per_page in 1..=3 {
+            let options = PdfOptions {
+                puzzles_per_page,
+                ..Default::default()
+            };
+            let slot_height = (PAGE_HEIGHT - 2.0 * PAGE_MARGIN) / puzzles_per_page as f32;
+            for slot in 0..puzzles_per_page {
+                let layout = sheet_layout(&sheet, slot, &options);
+                let slot_top = PAGE_MARGIN + slot_height * slot as f32;
+                assert!(layout.start_x >= PAGE_MARGIN);
+                assert!(layout.start_x + layout.grid_width() <= PAGE_WIDTH - PAGE_MARGIN);
+                assert!(layout.start_y > slot_top);
+                let (x, y) = word_position(&sheet, &layout, words.len() - 1, &options);
+                assert!(x < PAGE_WIDTH - PAGE_MARGIN);
+                assert!(y <= slot_top + slot_height + 0.01);
+            }
+        }
+    }
+    #[test]
+    fn test_word_list_fills_columns() {
+        let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+        let words: Vec<String> = ["ab", "cd", "ac", "bd", "ad"].map(String::from).to_vec();
+        let sheet = Sheet {
+            title: String::new(),
+            board: &board,
+            words: &words,
+        };
+        let options = PdfOptions::default();
+        let layout = sheet_layout(&sheet, 0, &options);
+        // 5 words in 3 columns: 2 rows, filled top to bottom then left to right
+        let first = word_position(&sheet, &layout, 0, &options);
+        let second = word_position(&sheet, &layout, 1, &options);
+        let third = word_position(&sheet, &layout, 2, &options);
+        assert_eq!(first.0, second.0);
+        assert!(second.1 > first.1);
+        assert!(third.0 > first.0);
+        assert_eq!(third.1, first.1);
+    }
+    #[test]
+    fn test_render_pdf_pages() {
+        let board = Board::new(&fetch_board(Path::new("src/input/board_months.txt")));
+        let words = fetch_target_words(Path::new("src/input/months.txt"));
+        let sheets: Vec<Sheet> = (0..3)
+            .map(|i| Sheet {
+                title: format!("Months {}", i + 1),
+                board: &board,
+                words: &words,
+            })
+            .collect();
+        let options = PdfOptions {
+            puzzles_per_page: 2,
+            word_columns: 4,
+            answer_key: true,
+        };
+        let pdf = render_pdf(&sheets, &options).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        // 2 puzzle pages and 2 answer key pages
+        assert_eq!(count_pages(&pdf), 4);
+        assert!(render_pdf(&[], &options).is_err());
+    }
+}
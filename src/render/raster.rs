@@ -0,0 +1,296 @@
+use crate::board::Board;
+use crate::constant::*;
+use crate::layout::Layout;
+use crate::matches::Match;
+use crate::render::FONT_DATA;
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use std::path::Path;
+use tiny_skia::{Color, FillRule, Mask, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+/// Blank space around the grid and the word list, in pixel
+pub const MARGIN: f32 = 50.0;
+/// Room reserved on the right of the word list anchor, in pixel
+pub const WORD_COLUMN_WIDTH: f32 = 300.0;
+const FONT_SCALE: f32 = 30.0;
+
+/// Layout used for images: same cell size as the visualizer, but without the empty space on the left
+pub fn image_layout(board: &Board) -> Layout {
+    Layout::new(
+        MARGIN,
+        MARGIN,
+        GRID_SIZE,
+        board.get_rows(),
+        board.get_cols(),
+    )
+}
+
+/// A software rendered image of a board, drawn with the same layout as the ggez visualizer.
+/// Needs neither a window nor a GPU, so it runs on headless CI.
+#[derive(Clone)]
+pub struct Frame {
+    pub pixmap: Pixmap,
+    pub layout: Layout,
+    font: FontRef<'static>,
+}
+impl Frame {
+    /// Create a white frame big enough for the board and `word_count` entries in the word list
+    pub fn new(board: &Board, word_count: usize) -> Self {
+        let layout = image_layout(board);
+        let (list_x, list_y) = layout.word_list_pos(word_count);
+        let width = list_x + WORD_COLUMN_WIDTH;
+        let height = f32::max(layout.start_y + layout.grid_height(), list_y) + MARGIN;
+        let mut pixmap = Pixmap::new(width.ceil() as u32, height.ceil() as u32)
+            .expect("image size should not be zero");
+        pixmap.fill(Color::WHITE);
+        Frame {
+            pixmap,
+            layout,
+            font: FontRef::try_from_slice(FONT_DATA).expect("bundled font should be valid"),
+        }
+    }
+    /// Draw the border of the grid, like `build_grid`
+    pub fn draw_grid(&mut self) {
+        let rect = Rect::from_xywh(
+            self.layout.start_x,
+            self.layout.start_y,
+            self.layout.grid_width(),
+            self.layout.grid_height(),
+        )
+        .unwrap();
+        let path = PathBuilder::from_rect(rect);
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(255, 0, 0, 255);
+        let stroke = Stroke {
+            width: 1.0,
+            ..Default::default()
+        };
+        self.pixmap
+            .stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    }
+    /// Draw every letter of the board, centered in its cell
+    pub fn draw_letters(&mut self, board: &Board) {
+        let mut mask = self.new_mask();
+        for (i, row) in board.letters.iter().enumerate() {
+            for (j, letter) in row.iter().enumerate() {
+                let (x, y) = self.layout.cell_center(i, j);
+                self.draw_text(&mut mask, &letter.to_string(), x, y, true);
+            }
+        }
+        self.fill_mask(&mask);
+    }
+    /// Draw the words on the right of the grid, like `display_word`
+    pub fn draw_word_list<S: AsRef<str>>(&mut self, words: &[S]) {
+        let mut mask = self.new_mask();
+        for (idx, word) in words.iter().enumerate() {
+            let (x, y) = self.layout.word_list_pos(idx);
+            self.draw_text(&mut mask, word.as_ref(), x, y, false);
+        }
+        self.fill_mask(&mask);
+    }
+    /// Draw a single word at position `idx` of the word list
+    pub fn draw_word(&mut self, word: &str, idx: usize) {
+        let mut mask = self.new_mask();
+        let (x, y) = self.layout.word_list_pos(idx);
+        self.draw_text(&mut mask, word, x, y, false);
+        self.fill_mask(&mask);
+    }
+    /// Draw a persistent strike line between the (row, column) of two cells, like `draw_strikes`
+    pub fn draw_strike(&mut self, start: (usize, usize), end: (usize, usize)) {
+        let start = self.layout.cell_center(start.0, start.1);
+        let end = self.layout.cell_center(end.0, end.1);
+        self.stroke_line(start, end, 5.0, [0, 0, 255, 255]);
+    }
+    /// Draw the line currently checked by the search, like `draw_highlighted_line`
+    /// # Arguments
+    /// * `start` - The (row, column) of the first cell of the probe
+    /// * `end` - The (row, column) of the last cell of the probe
+    pub fn draw_probe(&mut self, start: (usize, usize), end: (usize, usize)) {
+        let start = self.layout.cell_center(start.0, start.1);
+        let end = self.layout.cell_center(end.0, end.1);
+        if start == end {
+            let circle = PathBuilder::from_circle(start.0, start.1, self.layout.grid_size / 2.0);
+            let mut paint = Paint::default();
+            paint.set_color_rgba8(255, 0, 0, 128);
+            self.pixmap.fill_path(
+                &circle.unwrap(),
+                &paint,
+                FillRule::Winding,
+                Transform::identity(),
+                None,
+            );
+        } else {
+            self.stroke_line(start, end, 10.0, [0, 255, 0, 255]);
+        }
+    }
+    pub fn save_png(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        self.pixmap.save_png(path)?;
+        Ok(())
+    }
+
+    fn stroke_line(&mut self, start: (f32, f32), end: (f32, f32), width: f32, rgba: [u8; 4]) {
+        let mut pb = PathBuilder::new();
+        pb.move_to(start.0, start.1);
+        pb.line_to(end.0, end.1);
+        // A single cell word gives an empty path, draw a dot instead
+        if start == end {
+            pb.line_to(end.0 + 0.1, end.1);
+        }
+        let path = pb.finish().unwrap();
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(rgba[0], rgba[1], rgba[2], rgba[3]);
+        let stroke = Stroke {
+            width,
+            ..Default::default()
+        };
+        self.pixmap
+            .stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    }
+    fn new_mask(&self) -> Mask {
+        Mask::new(self.pixmap.width(), self.pixmap.height()).unwrap()
+    }
+    // Fill the coverage collected in the mask with black, the text color of the visualizer
+    fn fill_mask(&mut self, mask: &Mask) {
+        let rect = Rect::from_xywh(
+            0.0,
+            0.0,
+            self.pixmap.width() as f32,
+            self.pixmap.height() as f32,
+        )
+        .unwrap();
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(0, 0, 0, 255);
+        self.pixmap
+            .fill_rect(rect, &paint, Transform::identity(), Some(mask));
+    }
+    // Rasterize a line of text into the mask, either centered on (x, y) or with (x, y) as its top-left
+    fn draw_text(&self, mask: &mut Mask, text: &str, x: f32, y: f32, centered: bool) {
+        let font = self.font.as_scaled(PxScale::from(FONT_SCALE));
+        let mut width = 0.0;
+        let mut last = None;
+        for c in text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(prev) = last {
+                width += font.kern(prev, id);
+            }
+            width += font.h_advance(id);
+            last = Some(id);
+        }
+        let height = font.ascent() - font.descent();
+        let (origin_x, origin_y) = if centered {
+            (x - width / 2.0, y - height / 2.0)
+        } else {
+            (x, y)
+        };
+
+        let (mask_width, mask_height) = (mask.width() as i32, mask.height() as i32);
+        let data = mask.data_mut();
+        let mut caret = 0.0;
+        let mut last = None;
+        for c in text.chars() {
+            let mut glyph = font.scaled_glyph(c);
+            if let Some(prev) = last {
+                caret += font.kern(prev, glyph.id);
+            }
+            last = Some(glyph.id);
+            glyph.position = point(origin_x + caret, origin_y + font.ascent());
+            caret += font.h_advance(glyph.id);
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i32 + gx as i32;
+                let py = bounds.min.y as i32 + gy as i32;
+                if px < 0 || py < 0 || px >= mask_width || py >= mask_height {
+                    return;
+                }
+                let value = &mut data[(py * mask_width + px) as usize];
+                *value = u8::max(*value, (coverage * 255.0) as u8);
+            });
+        }
+    }
+}
+
+/// Render the blank puzzle: grid, letters and the words to find
+pub fn render_puzzle<S: AsRef<str>>(board: &Board, words: &[S]) -> Frame {
+    let mut frame = Frame::new(board, words.len());
+    frame.draw_grid();
+    frame.draw_letters(board);
+    frame.draw_word_list(words);
+    frame
+}
+
+/// Render the answer key: the puzzle with a strike line over every found word
+pub fn render_answer_key<S: AsRef<str>>(board: &Board, words: &[S], found: &[Match]) -> Frame {
+    let mut frame = render_puzzle(board, words);
+    for word_match in found {
+        frame.draw_strike(word_match.start, word_match.end);
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::solve;
+    use crate::trie::Trie;
+
+    fn board() -> Board {
+        Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ])
+    }
+    fn rgb(frame: &Frame, (x, y): (f32, f32)) -> (u8, u8, u8) {
+        let pixel = frame.pixmap.pixel(x as u32, y as u32).unwrap();
+        (pixel.red(), pixel.green(), pixel.blue())
+    }
+    #[test]
+    fn test_image_size_fits_grid_and_word_list() {
+        let b = board();
+        let frame = render_puzzle(&b, &["abc"; 10]);
+        let (_, list_y) = frame.layout.word_list_pos(10);
+        assert_eq!(
+            frame.pixmap.width(),
+            (MARGIN + 3.0 * GRID_SIZE + 100.0 + 300.0) as u32
+        );
+        assert_eq!(frame.pixmap.height(), (list_y + MARGIN) as u32);
+    }
+    #[test]
+    fn test_puzzle_layout() {
+        let b = board();
+        let frame = render_puzzle(&b, &["abc"]);
+        // Red grid border, the 1px stroke straddles two pixel columns
+        let (red, green, blue) = rgb(&frame, (MARGIN, MARGIN + 10.0));
+        assert_eq!(red, 255);
+        assert!(green < 200 && green == blue);
+        // Letters are drawn in black around the cell centers, the cell corners stay white
+        let (x, y) = frame.layout.cell_origin(1, 1);
+        assert_eq!(rgb(&frame, (x + 2.0, y + 2.0)), (255, 255, 255));
+        let has_ink = (0..GRID_SIZE as u32).any(|dx| {
+            let pixel = frame
+                .pixmap
+                .pixel(x as u32 + dx, (y + GRID_SIZE / 2.0) as u32);
+            pixel.unwrap().red() < 128
+        });
+        assert!(has_ink);
+    }
+    #[test]
+    fn test_answer_key_strikes_found_words() {
+        let b = board();
+        let trie = Trie::from(&vec!["abc", "aei"]);
+        let found = solve(&b, &trie);
+        let puzzle = render_puzzle(&b, &["abc", "aei"]);
+        let answer = render_answer_key(&b, &["abc", "aei"], &found);
+        // Between two cells of a found word the strike line is blue
+        let (x0, y0) = answer.layout.cell_center(0, 0);
+        let (x1, _) = answer.layout.cell_center(0, 1);
+        let middle = ((x0 + x1) / 2.0, y0);
+        assert_eq!(rgb(&answer, middle), (0, 0, 255));
+        assert_eq!(rgb(&puzzle, middle), (255, 255, 255));
+        // Cells that are not covered by any word are left untouched
+        let (x, y) = answer.layout.cell_origin(2, 0);
+        assert_eq!(rgb(&answer, (x + 2.0, y + 2.0)), (255, 255, 255));
+    }
+}
@@ -0,0 +1,216 @@
+use crate::board::{Board, WordPosition};
+use crate::game::GameProgress;
+use crate::playback::Playback;
+use crate::strategy::STRATEGY_NAMES;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const HEADER: &str = "# word search session v1";
+
+/// Everything needed to pick a visualizer session up where it was left
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Session {
+    pub board: Board,
+    pub words: Vec<String>,
+    /// Name of the running strategy, one of `STRATEGY_NAMES`
+    pub strategy: String,
+    /// Steps of the search run so far. The search is deterministic, running them again
+    /// rebuilds the search state, the found words, the trace, the heatmap and the stats.
+    pub steps: usize,
+    /// Time the search had been running for, or took once it was finished
+    pub elapsed: Duration,
+    pub playback: Playback,
+    /// Progress of play mode, when the player was looking for the words
+    pub game: Option<GameProgress>,
+}
+impl Session {
+    /// Write the session as one `key values` line per field, the board one row per line
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::playback::Playback;
+    /// use word_search_solver::session::Session;
+    /// use std::time::Duration;
+    /// let session = Session {
+    ///     board: Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]),
+    ///     words: vec!["ab".to_string()],
+    ///     strategy: "trie".to_string(),
+    ///     steps: 12,
+    ///     elapsed: Duration::from_millis(400),
+    ///     playback: Playback::new(),
+    ///     game: None,
+    /// };
+    /// let text = session.to_text();
+    /// assert!(text.contains("board ab\nboard cd\nword ab\nstrategy trie\nsteps 12\nelapsed 400\n"));
+    /// assert_eq!(Session::parse(&text), Ok(session));
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![HEADER.to_string()];
+        for row in self.board.letters.iter() {
+            lines.push(format!("board {}", row.iter().collect::<String>()));
+        }
+        for word in self.words.iter() {
+            lines.push(format!("word {}", word));
+        }
+        lines.push(format!("strategy {}", self.strategy));
+        lines.push(format!("steps {}", self.steps));
+        lines.push(format!("elapsed {}", self.elapsed.as_millis()));
+        lines.push(format!("paused {}", self.playback.paused));
+        lines.push(format!("speed {}", self.playback.steps_per_second));
+        if let Some(game) = &self.game {
+            lines.push(format!(
+                "game {} {} {} {}",
+                game.score,
+                game.mistakes,
+                game.hints_used,
+                game.elapsed.as_millis()
+            ));
+            for placement in game.found.iter() {
+                let (start, end) = (placement.start(), placement.end());
+                lines.push(format!("found {} {} {} {}", start.0, start.1, end.0, end.1));
+            }
+            if let Some((word, level)) = &game.hint {
+                lines.push(format!("hint {} {}", word, level));
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+    /// Read a session written by `to_text`, blank lines and lines starting with `#` are ignored
+    pub fn parse(contents: &str) -> Result<Session, String> {
+        let mut letters: Vec<Vec<char>> = Vec::new();
+        let mut words = Vec::new();
+        let mut strategy = STRATEGY_NAMES[0].to_string();
+        let mut steps = 0;
+        let mut elapsed = Duration::ZERO;
+        let mut playback = Playback::new();
+        let mut game: Option<GameProgress> = None;
+        for (idx, line) in contents.lines().enumerate() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let invalid = || format!("Invalid session on line {}: {}", idx + 1, line);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let numbers = || -> Result<Vec<u64>, String> {
+                fields[1..]
+                    .iter()
+                    .map(|field| field.parse().map_err(|_| invalid()))
+                    .collect()
+            };
+            match fields[..] {
+                ["board", row] => letters.push(row.chars().collect()),
+                ["word", word] => words.push(word.to_string()),
+                ["strategy", name] if STRATEGY_NAMES.contains(&name) => strategy = name.to_string(),
+                ["steps", count] => steps = count.parse().map_err(|_| invalid())?,
+                ["elapsed", millis] => {
+                    elapsed = Duration::from_millis(millis.parse().map_err(|_| invalid())?)
+                }
+                ["paused", paused] => playback.paused = paused.parse().map_err(|_| invalid())?,
+                ["speed", speed] => {
+                    playback.steps_per_second = speed.parse().map_err(|_| invalid())?
+                }
+                ["game", _, _, _, _] => {
+                    let numbers = numbers()?;
+                    game = Some(GameProgress {
+                        score: numbers[0] as u32,
+                        mistakes: numbers[1] as usize,
+                        hints_used: numbers[2] as usize,
+                        elapsed: Duration::from_millis(numbers[3]),
+                        ..game.unwrap_or_default()
+                    });
+                }
+                ["found", _, _, _, _] => {
+                    let cells: Vec<usize> = numbers()?.iter().map(|n| *n as usize).collect();
+                    let placement = WordPosition::new((cells[0], cells[1]), (cells[2], cells[3]));
+                    game.get_or_insert_with(GameProgress::default)
+                        .found
+                        .push(placement);
+                }
+                ["hint", word, level] => {
+                    let level = level.parse().map_err(|_| invalid())?;
+                    game.get_or_insert_with(GameProgress::default).hint =
+                        Some((word.to_string(), level));
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(Session {
+            board: Board::try_from(letters)?,
+            words,
+            strategy,
+            steps,
+            elapsed,
+            playback,
+            game,
+        })
+    }
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+    pub fn load(path: &Path) -> Result<Session, Box<dyn std::error::Error>> {
+        Ok(Session::parse(&fs::read_to_string(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn test_game_progress_round_trip() {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let words = ["abc", "aei", "hg"].map(String::from).to_vec();
+        let mut game = Game::new(board.clone(), words.clone());
+        game.select((2, 2), (0, 0));
+        game.select((0, 0), (1, 0));
+        game.hint();
+        game.hint();
+        let session = Session {
+            board: board.clone(),
+            words: words.clone(),
+            strategy: "brute-force".to_string(),
+            steps: 40,
+            elapsed: Duration::from_millis(2_250),
+            playback: Playback {
+                paused: true,
+                steps_per_second: 120,
+            },
+            game: Some(GameProgress {
+                elapsed: Duration::from_millis(61_500),
+                ..game.progress()
+            }),
+        };
+        let text = session.to_text();
+        assert!(text.contains("game 10 1 2 61500\nfound 0 0 2 2\nhint abc 2\n"));
+        let parsed = Session::parse(&text).unwrap();
+        assert_eq!(parsed, session);
+
+        let resumed = Game::resume(board, words, parsed.game.as_ref().unwrap()).unwrap();
+        assert_eq!(resumed.found, game.found);
+        assert_eq!(resumed.current_hint(), game.current_hint());
+        assert!(resumed.elapsed() >= Duration::from_millis(61_500));
+        assert_eq!(resumed.remaining_words(), vec!["abc", "hg"]);
+
+        // A placement that is not a word of the puzzle cannot be resumed
+        let broken = text.replace("found 0 0 2 2", "found 0 0 1 1");
+        let progress = Session::parse(&broken).unwrap().game.unwrap();
+        let board = session.board.clone();
+        assert!(Game::resume(board, session.words.clone(), &progress).is_err());
+
+        assert!(Session::parse("board ab\nboard c\n").is_err());
+        assert!(Session::parse("board ab\nstrategy bogo\n").is_err());
+        assert_eq!(
+            Session::parse("board ab\nsteps many\n"),
+            Err("Invalid session on line 2: steps many".to_string())
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::matches::Match;
+use crate::state::search_state::SearchState;
+use crate::stats::SearchStats;
+use crate::trace::TraceStep;
+use crate::trie::Trie;
+use std::collections::HashSet;
+
+/// Run the stepping engine over the whole board without any rendering, the same way
+/// `MainState::update` does one step per frame
+/// # Arguments
+/// * `board` - The board to search
+/// * `trie` - The trie built from the target words
+/// # Returns
+/// * `Vec<Match>` - Every word found, in the order the engine found them
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::solver::solve;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+/// let trie = Trie::from(&vec!["abc", "aei", "bg"]);
+/// let found = solve(&board, &trie);
+/// assert_eq!(found.len(), 2);
+/// assert_eq!(found[0].position().to_1d(3), (0, 2));
+/// assert_eq!(found[1].word, "aei");
+/// ```
+pub fn solve(board: &Board, trie: &Trie) -> Vec<Match> {
+    let mut state = SearchState::new();
+    let mut found = Vec::new();
+    loop {
+        found.extend(board.check_state(&mut state, trie));
+        match board.next_state(&state, state.feasible) {
+            Some(next) => state = next,
+            None => break,
+        }
+    }
+    found
+}
+
+/// Same as `solve`, and also count the steps, prunes and words of the search, which costs a
+/// prefix lookup per step
+pub fn solve_with_stats(board: &Board, trie: &Trie) -> (Vec<Match>, SearchStats) {
+    let mut state = SearchState::new();
+    let mut found = Vec::new();
+    let mut stats = SearchStats::new(board, trie);
+    loop {
+        let word = board.check_state(&mut state, trie);
+        let word_position = word.as_ref().map(Match::position);
+        found.extend(word);
+        stats.record(
+            board,
+            trie,
+            &TraceStep {
+                position: state.position,
+                direction: state.direction,
+                distance: state.distance,
+                feasible: state.feasible,
+                found: word_position,
+            },
+        );
+        match board.next_state(&state, state.feasible) {
+            Some(next) => state = next,
+            None => break,
+        }
+    }
+    (found, stats)
+}
+
+/// Run the stepping engine on the probes starting from one cell only, in every direction.
+/// The words found from every cell in row-major order are the words found by `solve`.
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::solver::solve_from;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+/// let trie = Trie::from(&vec!["ab", "db"]);
+/// let found = solve_from(&board, &trie, (0, 0));
+/// assert_eq!(found.len(), 1);
+/// assert_eq!((found[0].start, found[0].end), ((0, 0), (0, 1)));
+/// assert!(solve_from(&board, &trie, (1, 0)).is_empty());
+/// ```
+pub fn solve_from(board: &Board, trie: &Trie, cell: (usize, usize)) -> Vec<Match> {
+    let mut state = SearchState::from(cell, Direction::Up, 0);
+    let mut found = Vec::new();
+    while state.position == cell {
+        found.extend(board.check_state(&mut state, trie));
+        match board.next_state(&state, state.feasible) {
+            Some(next) => state = next,
+            None => break,
+        }
+    }
+    found
+}
+
+/// Get the target words that are not in the found list
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::solver::{missing_words, solve};
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+/// let words = vec!["ab".to_string(), "xy".to_string()];
+/// let found = solve(&board, &Trie::from(&vec!["ab", "xy"]));
+/// assert_eq!(missing_words(&words, &found), vec!["xy".to_string()]);
+/// ```
+pub fn missing_words(words: &[String], found: &[Match]) -> Vec<String> {
+    let found_words: HashSet<&str> = found.iter().map(|m| m.word.as_str()).collect();
+    words
+        .iter()
+        .filter(|word| !found_words.contains(word.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// The stepping engine shared by the front ends: one call to `step` checks one
+/// (position, direction, distance) probe and moves to the next one. The front ends keep their
+/// own trace, heatmap and stats from the steps it returns.
+pub struct Searcher {
+    pub board: Board,
+    pub trie: Trie,
+    pub search_state: SearchState,
+    pub found: Vec<Match>,
+    pub steps: usize,
+    finished: bool,
+}
+impl Searcher {
+    pub fn new(board: Board, trie: Trie) -> Self {
+        Searcher {
+            board,
+            trie,
+            search_state: SearchState::new(),
+            found: Vec::new(),
+            steps: 0,
+            finished: false,
+        }
+    }
+    /// Check the current state and move to the next one
+    /// # Returns
+    /// * `Option<TraceStep>` - The probe this step checked, None once the search is finished
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::solver::Searcher;
+    /// use word_search_solver::trie::Trie;
+    /// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+    /// let mut searcher = Searcher::new(board, Trie::from(&vec!["ab"]));
+    /// let mut steps = Vec::new();
+    /// while let Some(step) = searcher.step() {
+    ///     steps.push(step);
+    /// }
+    /// assert_eq!(steps.len(), searcher.steps);
+    /// let found: Vec<_> = steps.iter().filter_map(|step| step.found).collect();
+    /// assert_eq!(found, vec![searcher.found[0].position()]);
+    /// assert!(searcher.is_finished());
+    /// ```
+    pub fn step(&mut self) -> Option<TraceStep> {
+        if self.finished {
+            return None;
+        }
+        self.steps += 1;
+        let found = self.board.check_state(&mut self.search_state, &self.trie);
+        let step = TraceStep {
+            position: self.search_state.position,
+            direction: self.search_state.direction,
+            distance: self.search_state.distance,
+            feasible: self.search_state.feasible,
+            found: found.as_ref().map(Match::position),
+        };
+        self.found.extend(found);
+        match self
+            .board
+            .next_state(&self.search_state, self.search_state.feasible)
+        {
+            Some(state) => self.search_state = state,
+            None => self.finished = true,
+        }
+        Some(step)
+    }
+    /// Start the search over on the same board
+    pub fn restart(&mut self) {
+        self.search_state = SearchState::new();
+        self.found.clear();
+        self.steps = 0;
+        self.finished = false;
+    }
+    /// Run the remaining steps at once
+    pub fn finish(&mut self) {
+        while !self.finished {
+            self.step();
+        }
+    }
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+    /// The line the next step will check
+    pub fn probe(&self) -> Option<WordPosition> {
+        if self.finished {
+            return None;
+        }
+        self.search_state.current_prefix()
+    }
+}
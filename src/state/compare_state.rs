@@ -0,0 +1,225 @@
+use crate::layout::Layout;
+use crate::playback::Playback;
+use crate::state::event_loop::FileDropHandler;
+use crate::strategy::SearchStrategy;
+use crate::theme::Theme;
+use crate::utils::*;
+use ggez::event::EventHandler;
+use ggez::graphics::{self, Canvas, Color};
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::{glam::*, Context, GameResult};
+
+/// Several search strategies running on the same board in side by side panes, one step each per tick
+pub struct CompareState {
+    pub strategies: Vec<Box<dyn SearchStrategy>>,
+    pub layouts: Vec<Layout>,
+    pub grid_meshes: Vec<graphics::Mesh>,
+    pub playback: Playback,
+    /// Ticks since the start, shared by every pane
+    pub ticks: usize,
+    pub theme: Theme,
+}
+impl CompareState {
+    pub fn new(
+        ctx: &mut Context,
+        strategies: Vec<Box<dyn SearchStrategy>>,
+    ) -> GameResult<CompareState> {
+        let (width, height) = ctx.gfx.drawable_size();
+        let layouts = pane_layouts(&strategies, width, height);
+        let theme = Theme::default();
+        let grid_meshes = layouts
+            .iter()
+            .map(|layout| build_grid(ctx, layout, &theme))
+            .collect();
+        ctx.gfx.add_font(
+            "Montserrat",
+            graphics::FontData::from_path(ctx, "/Montserrat-Bold.ttf")?,
+        );
+        Ok(CompareState {
+            strategies,
+            layouts,
+            grid_meshes,
+            playback: Playback::new(),
+            ticks: 0,
+            theme,
+        })
+    }
+    pub fn is_finished(&self) -> bool {
+        self.strategies.iter().all(|strategy| strategy.is_done())
+    }
+    /// Step every strategy that is not done yet
+    pub fn advance(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        self.ticks += 1;
+        for strategy in self.strategies.iter_mut() {
+            strategy.step();
+        }
+    }
+    pub fn restart(&mut self) {
+        for strategy in self.strategies.iter_mut() {
+            strategy.restart();
+        }
+        self.ticks = 0;
+    }
+    // Build the grid of every pane again, after the layouts or the theme changed
+    fn build_grids(&mut self, ctx: &mut Context) {
+        self.grid_meshes = self
+            .layouts
+            .iter()
+            .map(|layout| build_grid(ctx, layout, &self.theme))
+            .collect();
+    }
+    fn draw_pane(&self, ctx: &mut Context, canvas: &mut Canvas, idx: usize) -> GameResult {
+        let (strategy, layout) = (&self.strategies[idx], &self.layouts[idx]);
+        let board = strategy.board();
+        canvas.draw(&self.grid_meshes[idx], graphics::DrawParam::new());
+        for (i, row) in board.letters.iter().enumerate() {
+            for (j, letter) in row.iter().enumerate() {
+                canvas.draw(
+                    graphics::Text::new(*letter)
+                        .set_scale(layout.grid_size * 0.6)
+                        .set_font("Montserrat"),
+                    graphics::DrawParam::new()
+                        .dest(Vec2::from(layout.cell_center(i, j)))
+                        .color(Color::from(self.theme.text))
+                        .offset(Vec2::new(0.5, 0.5)),
+                );
+            }
+        }
+        draw_strikes(ctx, canvas, layout, strategy.found(), &self.theme)?;
+        if let Some(probe) = strategy.probe() {
+            let (start, end) = probe.to_vec2();
+            draw_highlighted_line(ctx, layout, start, end, canvas, true, &self.theme);
+        }
+
+        canvas.draw(
+            graphics::Text::new(strategy.name())
+                .set_scale(36.)
+                .set_font("Montserrat"),
+            graphics::DrawParam::new()
+                .dest([layout.start_x, layout.start_y - 60.0])
+                .color(Color::from(self.theme.text)),
+        );
+        let steps = if strategy.is_done() {
+            format!("Done in {} steps", strategy.steps())
+        } else {
+            format!("Steps: {}", strategy.steps())
+        };
+        let lines = [steps, format!("Words found: {}", strategy.found().len())];
+        for (line_idx, line) in lines.iter().enumerate() {
+            canvas.draw(
+                graphics::Text::new(line.as_str())
+                    .set_scale(24.)
+                    .set_font("Montserrat"),
+                graphics::DrawParam::new()
+                    .dest([
+                        layout.start_x,
+                        layout.start_y + layout.grid_height() + 20.0 + 32.0 * line_idx as f32,
+                    ])
+                    .color(Color::from(self.theme.panel)),
+            );
+        }
+        Ok(())
+    }
+}
+
+// Split a `width` x `height` window into one pane per strategy
+fn pane_layouts(strategies: &[Box<dyn SearchStrategy>], width: f32, height: f32) -> Vec<Layout> {
+    strategies
+        .iter()
+        .enumerate()
+        .map(|(idx, strategy)| {
+            let board = strategy.board();
+            Layout::pane(
+                idx,
+                strategies.len(),
+                board.get_rows(),
+                board.get_cols(),
+                width,
+                height,
+            )
+        })
+        .collect()
+}
+
+impl FileDropHandler for CompareState {}
+
+impl EventHandler for CompareState {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        // Same pacing as the main visualizer, every pane moves on the same tick
+        while ctx.time.check_update_time(self.playback.steps_per_second) {
+            if self.playback.paused {
+                continue;
+            }
+            self.advance();
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let mut canvas = graphics::Canvas::from_frame(ctx, Color::from(self.theme.background));
+        for idx in 0..self.strategies.len() {
+            self.draw_pane(ctx, &mut canvas, idx)?;
+        }
+        let status = if self.is_finished() {
+            format!("Finished after {} ticks", self.ticks)
+        } else if self.playback.paused {
+            format!("Paused at tick {}", self.ticks)
+        } else {
+            format!(
+                "Tick {}    {} steps/s",
+                self.ticks, self.playback.steps_per_second
+            )
+        };
+        canvas.draw(
+            graphics::Text::new(format!(
+                "{}    [space] pause  [right] step  [+/-] speed  [end] skip to end  [r] restart  [c] theme",
+                status
+            ))
+            .set_scale(20.)
+            .set_font("Montserrat"),
+            graphics::DrawParam::new()
+                .dest([20.0, ctx.gfx.drawable_size().1 - 40.0])
+                .color(Color::from(self.theme.panel)),
+        );
+        canvas.finish(ctx)
+    }
+
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) -> GameResult {
+        self.layouts = pane_layouts(&self.strategies, width, height);
+        self.build_grids(ctx);
+        Ok(())
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        input: KeyInput,
+        _repeated: bool,
+    ) -> GameResult {
+        match input.keycode {
+            Some(KeyCode::Escape) => ctx.request_quit(),
+            Some(KeyCode::Space) => self.playback.toggle_pause(),
+            Some(KeyCode::Right) => {
+                self.playback.paused = true;
+                self.advance();
+            }
+            Some(KeyCode::Plus | KeyCode::Equals | KeyCode::NumpadAdd) => self.playback.faster(),
+            Some(KeyCode::Minus | KeyCode::NumpadSubtract) => self.playback.slower(),
+            Some(KeyCode::End) => {
+                while !self.is_finished() {
+                    self.advance();
+                }
+            }
+            Some(KeyCode::R) => self.restart(),
+            Some(KeyCode::C) => {
+                self.theme = self.theme.next();
+                self.build_grids(ctx);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use ggez::event::{self, ErrorOrigin, EventHandler};
+use ggez::input::gamepad::gilrs;
+use ggez::input::keyboard::{KeyInput, KeyMods};
+use ggez::winit::dpi::LogicalPosition;
+use ggez::winit::event::{ElementState, Event, KeyboardInput, MouseScrollDelta, WindowEvent};
+use ggez::winit::event_loop::{ControlFlow, EventLoop};
+use ggez::{Context, GameError, GameResult};
+use std::path::PathBuf;
+
+/// States that take files dropped on the window
+pub trait FileDropHandler {
+    /// Called once per file, a drop of several files calls it for each of them.
+    /// `ErrorOrigin` has no variant for it, so an error is printed and the loop keeps going
+    /// instead of going through `EventHandler::on_error`.
+    fn file_dropped_event(&mut self, _ctx: &mut Context, _path: PathBuf) -> GameResult {
+        Ok(())
+    }
+}
+
+// The error of a callback and where it came from, as `EventHandler::on_error` takes them
+type EventResult = Result<(), (ErrorOrigin, GameError)>;
+
+// Tag the error of a callback with the callback it came from
+fn with_origin(origin: ErrorOrigin) -> impl Fn(GameError) -> (ErrorOrigin, GameError) {
+    move |error| (origin, error)
+}
+
+/// Run the main loop like `ggez::event::run` does, and also pass the files dropped on the
+/// window to the state, which `ggez::event::run` ignores.
+/// Mirrors `ggez::event::run` of ggez 0.9.3: every event it forwards is forwarded here the same
+/// way, and errors go through `EventHandler::on_error`. Check it again when upgrading ggez.
+pub fn run<S>(mut ctx: Context, event_loop: EventLoop<()>, mut state: S) -> !
+where
+    S: EventHandler + FileDropHandler + 'static,
+{
+    event_loop.run(move |mut event, _, control_flow| {
+        let ctx = &mut ctx;
+        if ctx.quit_requested {
+            ctx.quit_requested = false;
+            if quit(ctx, &mut state) {
+                *control_flow = ControlFlow::Exit;
+                return;
+            }
+        }
+        if !ctx.continuing {
+            *control_flow = ControlFlow::Exit;
+            return;
+        }
+        *control_flow = ControlFlow::Poll;
+        // Keeps the mouse, keyboard and window size of the context up to date
+        event::process_event(ctx, &mut event);
+        let result = match event {
+            Event::WindowEvent { event, .. } => window_event(ctx, &mut state, event),
+            Event::MainEventsCleared => {
+                if frame(ctx, &mut state) {
+                    *control_flow = ControlFlow::Exit;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        };
+        if catch_error(ctx, &mut state, result) {
+            *control_flow = ControlFlow::Exit;
+        }
+    })
+}
+
+// Call the callback of a window event
+fn window_event<S>(ctx: &mut Context, state: &mut S, event: WindowEvent) -> EventResult
+where
+    S: EventHandler + FileDropHandler,
+{
+    match event {
+        WindowEvent::Resized(size) => state
+            .resize_event(ctx, size.width as f32, size.height as f32)
+            .map_err(with_origin(ErrorOrigin::ResizeEvent)),
+        WindowEvent::CloseRequested => {
+            if quit(ctx, state) {
+                ctx.continuing = false;
+            }
+            Ok(())
+        }
+        WindowEvent::Focused(gained) => state
+            .focus_event(ctx, gained)
+            .map_err(with_origin(ErrorOrigin::FocusEvent)),
+        WindowEvent::DroppedFile(path) => {
+            if let Err(error) = state.file_dropped_event(ctx, path) {
+                eprintln!(
+                    "Error in the visualizer, in file_dropped_event: {:?}",
+                    error
+                );
+            }
+            Ok(())
+        }
+        WindowEvent::ReceivedCharacter(ch) => state
+            .text_input_event(ctx, ch)
+            .map_err(with_origin(ErrorOrigin::TextInputEvent)),
+        WindowEvent::ModifiersChanged(mods) => {
+            ctx.keyboard.set_modifiers(KeyMods::from(mods));
+            Ok(())
+        }
+        WindowEvent::KeyboardInput {
+            input:
+                KeyboardInput {
+                    state: key_state,
+                    virtual_keycode: keycode,
+                    scancode,
+                    ..
+                },
+            ..
+        } => {
+            let input = KeyInput {
+                scancode,
+                keycode,
+                mods: ctx.keyboard.active_mods(),
+            };
+            match key_state {
+                ElementState::Pressed => {
+                    let repeated = ctx.keyboard.is_key_repeated();
+                    state
+                        .key_down_event(ctx, input, repeated)
+                        .map_err(with_origin(ErrorOrigin::KeyDownEvent))
+                }
+                ElementState::Released => state
+                    .key_up_event(ctx, input)
+                    .map_err(with_origin(ErrorOrigin::KeyUpEvent)),
+            }
+        }
+        WindowEvent::MouseWheel { delta, .. } => {
+            let (x, y) = match delta {
+                MouseScrollDelta::LineDelta(x, y) => (x, y),
+                MouseScrollDelta::PixelDelta(pos) => {
+                    let scale_factor = ctx.gfx.window().scale_factor();
+                    let LogicalPosition { x, y } = pos.to_logical::<f32>(scale_factor);
+                    (x, y)
+                }
+            };
+            state
+                .mouse_wheel_event(ctx, x, y)
+                .map_err(with_origin(ErrorOrigin::MouseWheelEvent))
+        }
+        WindowEvent::MouseInput {
+            state: button_state,
+            button,
+            ..
+        } => {
+            let position = ctx.mouse.position();
+            match button_state {
+                ElementState::Pressed => state
+                    .mouse_button_down_event(ctx, button, position.x, position.y)
+                    .map_err(with_origin(ErrorOrigin::MouseButtonDownEvent)),
+                ElementState::Released => state
+                    .mouse_button_up_event(ctx, button, position.x, position.y)
+                    .map_err(with_origin(ErrorOrigin::MouseButtonUpEvent)),
+            }
+        }
+        WindowEvent::CursorMoved { .. } => {
+            let (position, delta) = (ctx.mouse.position(), ctx.mouse.last_delta());
+            state
+                .mouse_motion_event(ctx, position.x, position.y, delta.x, delta.y)
+                .map_err(with_origin(ErrorOrigin::MouseMotionEvent))
+        }
+        WindowEvent::Touch(touch) => state
+            .touch_event(ctx, touch.phase, touch.location.x, touch.location.y)
+            .map_err(with_origin(ErrorOrigin::TouchEvent)),
+        WindowEvent::CursorEntered { .. } => state
+            .mouse_enter_or_leave(ctx, true)
+            .map_err(with_origin(ErrorOrigin::MouseEnterOrLeave)),
+        WindowEvent::CursorLeft { .. } => state
+            .mouse_enter_or_leave(ctx, false)
+            .map_err(with_origin(ErrorOrigin::MouseEnterOrLeave)),
+        _ => Ok(()),
+    }
+}
+
+// Let the state handle the error of a callback, it ends the loop by returning true
+fn catch_error<S: EventHandler>(ctx: &mut Context, state: &mut S, result: EventResult) -> bool {
+    match result {
+        Ok(()) => false,
+        Err((origin, error)) => {
+            eprintln!("Error in the visualizer, in {:?}: {:?}", origin, error);
+            state.on_error(ctx, origin, error)
+        }
+    }
+}
+
+// Ask the state whether to quit, it keeps the window open by returning true.
+// Returns true when the loop should end right away on an error.
+fn quit<S: EventHandler>(ctx: &mut Context, state: &mut S) -> bool {
+    match state.quit_event(ctx) {
+        Ok(true) => false,
+        Ok(false) => {
+            ctx.continuing = false;
+            false
+        }
+        Err(error) => catch_error(ctx, state, Err((ErrorOrigin::QuitEvent, error))),
+    }
+}
+
+// Pass the gamepad events of the frame, then update and draw it.
+// Returns true when the loop should end.
+fn frame<S: EventHandler>(ctx: &mut Context, state: &mut S) -> bool {
+    ctx.time.tick();
+    while let Some(gilrs::Event { id, event, .. }) = ctx.gamepad.next_event() {
+        // `GamepadId` cannot be built outside of ggez, it is looked up among the connected gamepads
+        let Some(id) = ctx
+            .gamepad
+            .gamepads()
+            .find(|(_, gamepad)| gamepad.id() == id)
+            .map(|(id, _)| id)
+        else {
+            continue;
+        };
+        let result = match event {
+            gilrs::EventType::ButtonPressed(button, _) => state
+                .gamepad_button_down_event(ctx, button, id)
+                .map_err(with_origin(ErrorOrigin::GamepadButtonDownEvent)),
+            gilrs::EventType::ButtonReleased(button, _) => state
+                .gamepad_button_up_event(ctx, button, id)
+                .map_err(with_origin(ErrorOrigin::GamepadButtonUpEvent)),
+            gilrs::EventType::AxisChanged(axis, value, _) => state
+                .gamepad_axis_event(ctx, axis, value, id)
+                .map_err(with_origin(ErrorOrigin::GamepadAxisEvent)),
+            _ => Ok(()),
+        };
+        if catch_error(ctx, state, result) {
+            return true;
+        }
+    }
+    let result = state.update(ctx).map_err(with_origin(ErrorOrigin::Update));
+    if catch_error(ctx, state, result) {
+        return true;
+    }
+    // Like ggez, an error of the graphics context itself always ends the loop
+    if let Err(error) = ctx.gfx.begin_frame() {
+        eprintln!("Error in the visualizer, in begin_frame: {:?}", error);
+        return true;
+    }
+    let result = state.draw(ctx).map_err(with_origin(ErrorOrigin::Draw));
+    if catch_error(ctx, state, result) {
+        return true;
+    }
+    if let Err(error) = ctx.gfx.end_frame() {
+        eprintln!("Error in the visualizer, in end_frame: {:?}", error);
+        return true;
+    }
+    // The mouse delta adds up over a frame, and the key and button states are compared to the last frame
+    ctx.mouse.reset_delta();
+    ctx.keyboard.save_keyboard_state();
+    ctx.mouse.save_mouse_state();
+    false
+}
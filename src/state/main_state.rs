@@ -1,55 +1,192 @@
-use crate::board::Board;
-use crate::constant::*;
-use crate::state::search_state::SearchState;
+use crate::batch::puzzle_at;
+use crate::board::{Board, Direction, WordPosition};
+use crate::editor::Editor;
+use crate::game::{Game, Hint, Selection};
+use crate::heatmap::Heatmap;
+use crate::layout::Layout;
+use crate::matches::Match;
+use crate::playback::Playback;
+use crate::session::Session;
+use crate::solver::missing_words;
+use crate::state::event_loop::FileDropHandler;
+use crate::stats::SearchStats;
+use crate::strategy::{by_name, SearchStrategy, StepEvent, STRATEGY_NAMES};
+use crate::theme::Theme;
+use crate::trace::{Replay, Trace, TraceStep};
 use crate::trie::Trie;
 use crate::utils::*;
 use ggez::event::EventHandler;
 use ggez::graphics::{self};
+use ggez::input::keyboard::{KeyCode, KeyInput, KeyMods};
+use ggez::input::mouse::MouseButton;
 use ggez::{glam::*, Context, GameResult};
-use std::path::Path;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Puzzles shipped with the repository, the load key cycles through them
+pub const PUZZLES: [(&str, &str); 2] = [
+    ("src/input/board_months.txt", "src/input/months.txt"),
+    ("src/input/board.txt", "src/input/words.txt"),
+];
+/// Files an edited puzzle is saved to when it was not opened from files
+pub const EDITED_PUZZLE: (&str, &str) = ("board_edited.txt", "edited.txt");
+/// File the session is saved to when the window closes, and loaded from by the load session key
+pub const SESSION_PATH: &str = "session.txt";
+
+/// What is drawn in the cells of the grid, cycled by the view key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum View {
+    #[default]
+    Letters,
+    /// Search effort per cell, with the visit and prune counts instead of the letters
+    Heatmap,
+    /// The letters over the heatmap
+    Both,
+}
+impl View {
+    pub fn next(&self) -> View {
+        match self {
+            View::Letters => View::Heatmap,
+            View::Heatmap => View::Both,
+            View::Both => View::Letters,
+        }
+    }
+    pub fn shows_heatmap(&self) -> bool {
+        *self != View::Letters
+    }
+}
 
 pub struct MainState {
     pub grid_mesh: graphics::Mesh,
-    pub line_mesh: graphics::Mesh,
-    pub board_state: Board,
-    pub mb: graphics::MeshBuilder,
+    pub strategy: Box<dyn SearchStrategy>,
+    pub strategy_idx: usize, // Index of the running strategy in `STRATEGY_NAMES`
     pub trie: Trie,
-    pub found_words_idx: Vec<(usize, usize)>,
+    pub words: Vec<String>,
+    // Recorded from the steps of the running strategy, whichever it is
+    pub trace: Trace,
+    pub heatmap: Heatmap,
+    pub stats: SearchStats,
+    pub feasible: bool, // Whether the last probe can still lead to a word
+    pub found_words: Vec<Match>,
     pub current_idx: (Vec2, Vec2), // Current line position to check if it is a word
-    pub search_state: SearchState,
+    pub layout: Layout,
+    pub playback: Playback,
+    pub puzzle_idx: usize,
+    pub started: Instant,
+    pub finished_after: Option<Duration>, // Time the search took, set once it is finished
+    pub replay: Option<Replay>,           // Recorded trace played instead of the live search
+    pub dragging_timeline: bool,
+    pub game: Option<Game>, // Play mode, the player looks for the words instead of the solver
+    pub selection: Option<((usize, usize), (usize, usize))>, // Cells dragged over in play mode
+    pub rejected: Option<(WordPosition, Instant)>, // Wrong selection, animated back to its start
+    pub view: View,
+    pub prompt: Option<String>, // Path typed after the open key, until enter is pressed
+    pub prompt_opening: bool, // The letter of the open key is still to come through `text_input_event`
+    pub confirm_new_game: bool, // The new game key was pressed once in a game with words found
+    pub dropped: Vec<PathBuf>, // Board or word list dropped alone, waiting for the other one
+    pub puzzle_paths: Option<(PathBuf, PathBuf)>, // Board and word files of the puzzle, if it came from files
+    pub editor: Option<Editor>, // Edit mode, the puzzle is changed instead of searched
+    pub theme: Theme,           // Colors of everything drawn, cycled by the theme key
+}
+/// Time a wrong selection takes to shrink back to its first cell
+const REJECT_ANIMATION: Duration = Duration::from_millis(300);
+// Load board and target words
+fn load_puzzle(
+    board_file_path: &Path,
+    target_words_file_path: &Path,
+) -> Result<(Board, Vec<String>), String> {
+    for path in [board_file_path, target_words_file_path] {
+        if !path.is_file() {
+            return Err(format!("{} not found", path.display()));
+        }
+    }
+    let letters: Vec<Vec<char>> = fetch_board(board_file_path);
+    let board_state = Board::try_from(letters)
+        .map_err(|error| format!("{}: {}", board_file_path.display(), error))?;
+    let target_words =
+        read_target_words(target_words_file_path).map_err(|error| error.to_string())?;
+    Ok((board_state, target_words))
 }
 impl MainState {
     pub fn new(ctx: &mut Context) -> GameResult<MainState> {
-        // Load board and target words
-        let board_file_path = Path::new("src/input/board_months.txt");
-        let target_words_file_path = Path::new("src/input/months.txt");
-        let letters: Vec<Vec<char>> = fetch_board(board_file_path);
-        if letters.len() != BOARD_SIZE || letters[0].len() != BOARD_SIZE {
-            panic!("Board size is not correct, please check the input file or modify the BOARD_SIZE constant");
-        }
+        let (board_path, words_path) = PUZZLES[0];
+        MainState::with_puzzle(ctx, Path::new(board_path), Path::new(words_path))
+    }
+    pub fn with_puzzle(
+        ctx: &mut Context,
+        board_path: &Path,
+        words_path: &Path,
+    ) -> GameResult<MainState> {
+        let (board_state, target_words) =
+            load_puzzle(board_path, words_path).map_err(ggez::GameError::CustomError)?;
+        let mut state = MainState::with_board(ctx, board_state, target_words)?;
+        state.puzzle_paths = Some((board_path.to_path_buf(), words_path.to_path_buf()));
+        Ok(state)
+    }
+    /// Pick a saved session up where it was left
+    pub fn from_session(ctx: &mut Context, session: Session) -> GameResult<MainState> {
+        let mut state = MainState::with_board(ctx, session.board.clone(), session.words.clone())?;
+        state
+            .resume(ctx, session)
+            .map_err(ggez::GameError::CustomError)?;
+        Ok(state)
+    }
+    pub fn with_board(
+        ctx: &mut Context,
+        board_state: Board,
+        target_words: Vec<String>,
+    ) -> GameResult<MainState> {
         // Initialize grid
-        let grid_mesh = build_grid(ctx);
-
-        // Initialize mesh & mesh builder, for building persistent lines
-        let mb = &mut graphics::MeshBuilder::new();
-        let line_mesh = graphics::Mesh::from_data(ctx, mb.build());
-
-        let board_state = Board::new(&letters);
+        let (width, height) = ctx.gfx.drawable_size();
+        let layout = Layout::fit(
+            board_state.get_rows(),
+            board_state.get_cols(),
+            width,
+            height,
+            target_words.len(),
+        );
+        let theme = Theme::default();
+        let grid_mesh = build_grid(ctx, &layout, &theme);
 
         // Initialize trie
-        let target_words: Vec<String> = fetch_target_words(target_words_file_path);
         let target_words_str = target_words.iter().map(String::as_str).collect();
         let trie = Trie::from(&target_words_str);
+        let heatmap = Heatmap::new(board_state.get_rows(), board_state.get_cols());
+        let stats = SearchStats::new(&board_state, &trie);
         let s = MainState {
             // ...
             grid_mesh,
-            line_mesh,
-            board_state,
-            mb: graphics::MeshBuilder::new(),
+            strategy: by_name(STRATEGY_NAMES[0], board_state, trie.clone()).unwrap(),
+            strategy_idx: 0,
             trie,
-            found_words_idx: Vec::new(),
+            words: target_words,
+            trace: Trace::default(),
+            heatmap,
+            stats,
+            feasible: true,
+            found_words: Vec::new(),
             current_idx: (Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0)),
-            search_state: SearchState::new(),
+            layout,
+            playback: Playback::new(),
+            puzzle_idx: 0,
+            started: Instant::now(),
+            finished_after: None,
+            replay: None,
+            dragging_timeline: false,
+            game: None,
+            selection: None,
+            rejected: None,
+            view: View::default(),
+            prompt: None,
+            prompt_opening: false,
+            confirm_new_game: false,
+            dropped: Vec::new(),
+            puzzle_paths: None,
+            editor: None,
+            theme,
         };
         ctx.gfx.add_font(
             "Montserrat",
@@ -57,85 +194,883 @@ impl MainState {
         );
         Ok(s)
     }
+    /// Run one step of the search and keep track of the line to highlight
+    pub fn advance(&mut self) {
+        if let Some(replay) = self.replay.as_mut() {
+            replay.forward();
+            self.sync_replay();
+            return;
+        }
+        if let Some(pos) = self.strategy.probe() {
+            self.current_idx = pos.to_vec2();
+        }
+        if let StepEvent::Probe(step) = self.strategy.step() {
+            self.record(step);
+        }
+        if self.strategy.is_done() && self.finished_after.is_none() {
+            self.finished_after = Some(self.started.elapsed());
+        }
+    }
+    // Keep the found words, trace, heatmap and stats of the running strategy up to date
+    fn record(&mut self, step: TraceStep) {
+        let board = self.strategy.board();
+        self.heatmap.record(board, &self.trie, &step);
+        self.stats.record(board, &self.trie, &step);
+        self.feasible = step.feasible;
+        self.trace.steps.push(step);
+        // A step of the line scan can find several words at once
+        if step.found.is_some() {
+            let found = self.strategy.found();
+            let new_words = found.get(self.found_words.len()..).unwrap_or_default();
+            self.found_words.extend_from_slice(new_words);
+        }
+    }
+    pub fn is_finished(&self) -> bool {
+        match &self.replay {
+            Some(replay) => replay.is_finished(),
+            None => self.strategy.is_done(),
+        }
+    }
+    /// Swap in the next strategy of `STRATEGY_NAMES` and start the search over with it
+    pub fn next_strategy(&mut self) {
+        self.strategy_idx = (self.strategy_idx + 1) % STRATEGY_NAMES.len();
+        let board = self.strategy.board().clone();
+        self.strategy = by_name(STRATEGY_NAMES[self.strategy_idx], board, self.trie.clone())
+            .expect("every name of STRATEGY_NAMES should be known");
+        self.replay = None;
+        self.restart();
+    }
+    /// Play a recorded trace instead of searching, a trace that does not fit the current puzzle
+    /// is rejected
+    pub fn start_replay(&mut self, trace: Trace) -> Result<(), String> {
+        trace.validate(self.strategy.board())?;
+        self.restart();
+        self.replay = Some(Replay::new(trace, self.strategy.board(), &self.trie));
+        self.sync_replay();
+        Ok(())
+    }
+    /// Move the replay to a given step and redraw from there
+    pub fn seek_replay(&mut self, position: usize) {
+        if let Some(replay) = self.replay.as_mut() {
+            replay.seek(position);
+            self.sync_replay();
+        }
+    }
+    // Bring the found words, heatmap, stats and highlighted line to the replay position
+    fn sync_replay(&mut self) {
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        let (board, trie) = (self.strategy.board(), &self.trie);
+        self.found_words = replay.matches_played().to_vec();
+        // Moving forward only records the new steps, both start over when seeking backwards
+        if replay.position < self.stats.steps {
+            self.stats = SearchStats::new(board, trie);
+            self.heatmap.clear();
+        }
+        for step in &replay.trace.steps[self.stats.steps..replay.position] {
+            self.heatmap.record(board, trie, step);
+            self.stats.record(board, trie, step);
+        }
+        // A pruned probe is drawn the same way as in a live search
+        self.feasible = replay.current().is_some_and(|step| step.feasible);
+        if let Some(step) = replay.current() {
+            let (i, j) = step.position;
+            if let Some(end) = Board::get_pos_from_direction(i, j, &step.direction, step.distance) {
+                self.current_idx = WordPosition::new(step.position, end).to_vec2();
+            }
+        }
+    }
+    // Seek the replay to the step under the mouse on the timeline
+    fn seek_timeline(&mut self, x: f32) {
+        let Some(len) = self.replay.as_ref().map(Replay::len) else {
+            return;
+        };
+        let (start_x, _, width, _) = self.layout.timeline_rect();
+        let progress = ((x - start_x) / width).clamp(0.0, 1.0);
+        self.seek_replay((progress * len as f32).round() as usize);
+    }
+    /// Switch to play mode on the current puzzle, or start a new game if already playing
+    pub fn start_game(&mut self) {
+        self.game = Some(Game::new(self.strategy.board().clone(), self.words.clone()));
+        self.replay = None;
+        self.selection = None;
+        self.rejected = None;
+    }
+    /// Go back to watching the solver
+    pub fn stop_game(&mut self) {
+        self.game = None;
+        self.selection = None;
+        self.rejected = None;
+    }
+    // Check the dragged selection, a wrong one is animated back
+    fn end_selection(&mut self) {
+        let (Some((start, end)), Some(game)) = (self.selection.take(), self.game.as_mut()) else {
+            return;
+        };
+        match game.select(start, end) {
+            Selection::Found(_) | Selection::SingleCell => {}
+            _ => self.rejected = Some((WordPosition::new(start, end), Instant::now())),
+        }
+    }
+    // Word list, selection and score of play mode, drawn over the grid and the strike lines
+    fn draw_game(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) {
+        let Some(game) = &self.game else {
+            return;
+        };
+        for (idx, word) in game.words.iter().enumerate() {
+            // The swatch takes the color of the strike on the grid, which comes from the match
+            if let Some(word_match) = game.found.iter().find(|found| found.word == *word) {
+                display_crossed_word(ctx, canvas, &self.layout, word, idx, &self.theme);
+                let color = self.theme.word_color(word_match.index);
+                display_swatch(ctx, canvas, &self.layout, idx, color);
+            } else {
+                let color = graphics::Color::from(self.theme.text);
+                display_word(ctx, canvas, &self.layout, word, idx, color);
+            }
+        }
+        if let Some((start, end)) = self.selection {
+            let start = Vec2::new(start.1 as f32, start.0 as f32);
+            let end = Vec2::new(end.1 as f32, end.0 as f32);
+            draw_highlighted_line(ctx, &self.layout, start, end, canvas, true, &self.theme);
+        }
+        if let Some((word_position, at)) = self.rejected {
+            let progress = at.elapsed().as_secs_f32() / REJECT_ANIMATION.as_secs_f32();
+            if progress < 1.0 {
+                let (start, end) = (word_position.start(), word_position.end());
+                let start = Vec2::from(self.layout.cell_center(start.0, start.1));
+                let end = Vec2::from(self.layout.cell_center(end.0, end.1));
+                draw_rejected_line(
+                    ctx,
+                    canvas,
+                    start,
+                    end.lerp(start, progress),
+                    1.0 - progress,
+                    &self.theme,
+                );
+            }
+        }
+        if let Some(hint) = game.current_hint() {
+            // Pulse twice a second, the stronger hints only fade a little
+            let pulse = (self.started.elapsed().as_secs_f32() * 4.0 * std::f32::consts::PI).sin();
+            let alpha = match hint {
+                Hint::FirstLetter(_) => 0.5 + 0.4 * pulse,
+                _ => 0.7 + 0.1 * pulse,
+            };
+            draw_hint(ctx, canvas, &self.layout, &hint, alpha, &self.theme);
+        }
+        if game.is_finished() {
+            display_summary(ctx, canvas, &self.layout, &game.summary(), &self.theme);
+        }
+        let status = format!(
+            "Score {}    Time {:.0} s    {} words left",
+            game.score,
+            game.elapsed().as_secs_f32(),
+            game.remaining_words().len()
+        );
+        let keys = if self.confirm_new_game {
+            "[r] again to start over and lose the words found, any other key to keep playing"
+        } else {
+            "[drag] select a word  [h] hint  [r] new game  [p] watch the solver"
+        };
+        self.draw_status(canvas, &format!("{}    {}", status, keys));
+    }
+    // One line of status text under the grid, the path being typed takes its place
+    fn draw_status(&self, canvas: &mut graphics::Canvas, text: &str) {
+        let (x, y) = self.layout.cell_origin(self.layout.rows, 0);
+        let text = match &self.prompt {
+            Some(path) => format!(
+                "Open: {}_    [enter] open a puzzle dir, a board, or a board and its words  [esc] cancel",
+                path
+            ),
+            None => text.to_string(),
+        };
+        canvas.draw(
+            graphics::Text::new(text)
+                .set_scale(20.)
+                .set_font("Montserrat"),
+            graphics::DrawParam::new()
+                .dest([x, y + 10.0])
+                .color(graphics::Color::from(self.theme.panel)),
+        );
+    }
+    /// Switch to the next theme of `THEMES`
+    pub fn next_theme(&mut self, ctx: &mut Context) {
+        self.theme = self.theme.next();
+        self.grid_mesh = build_grid(ctx, &self.layout, &self.theme);
+    }
+    /// Switch to edit mode on the current puzzle
+    pub fn start_editing(&mut self) {
+        self.editor = Some(Editor::new(
+            self.strategy.board().clone(),
+            self.words.clone(),
+        ));
+        self.selection = None;
+        self.rejected = None;
+    }
+    /// Leave edit mode and search the edited puzzle, or play it in play mode
+    pub fn stop_editing(&mut self, ctx: &mut Context) {
+        if let Some(editor) = self.editor.take() {
+            let words = editor.word_list();
+            self.set_puzzle(ctx, editor.board, words);
+        }
+    }
+    /// Write the edited puzzle back to its files, or to `EDITED_PUZZLE` when it has none
+    pub fn save_edits(&mut self) -> std::io::Result<()> {
+        let Some(editor) = self.editor.as_mut() else {
+            return Ok(());
+        };
+        let (board_path, words_path) = self.puzzle_paths.clone().unwrap_or_else(|| {
+            let (board_path, words_path) = EDITED_PUZZLE;
+            (board_path.into(), words_path.into())
+        });
+        editor.save(&board_path, &words_path)?;
+        println!(
+            "Puzzle saved to {} and {}",
+            board_path.display(),
+            words_path.display()
+        );
+        self.puzzle_paths = Some((board_path, words_path));
+        Ok(())
+    }
+    // Keys of edit mode, the letters come through `text_input_event`
+    fn editor_key_down(&mut self, ctx: &mut Context, input: KeyInput) -> GameResult {
+        let Some(editor) = self.editor.as_mut() else {
+            return Ok(());
+        };
+        let ctrl = input.mods.contains(KeyMods::CTRL);
+        match input.keycode {
+            Some(KeyCode::F2) => self.stop_editing(ctx),
+            Some(KeyCode::Escape) if editor.selected_word.is_some() => editor.select_word(None),
+            Some(KeyCode::Escape) => self.stop_editing(ctx),
+            Some(KeyCode::S) if ctrl => self.save_edits()?,
+            // Ctrl and an arrow grows the board after the cursor, or shrinks it at the cursor
+            Some(KeyCode::Down) if ctrl => editor.insert_row(),
+            Some(KeyCode::Up) if ctrl => editor.remove_row(),
+            Some(KeyCode::Right) if ctrl => editor.insert_col(),
+            Some(KeyCode::Left) if ctrl => editor.remove_col(),
+            Some(KeyCode::Up) => editor.move_cursor(Direction::Up),
+            Some(KeyCode::Down) => editor.move_cursor(Direction::Down),
+            Some(KeyCode::Left) => editor.move_cursor(Direction::Left),
+            Some(KeyCode::Right) => editor.move_cursor(Direction::Right),
+            Some(KeyCode::Back) => editor.backspace(),
+            Some(KeyCode::Tab) => editor.add_word(),
+            Some(KeyCode::Delete) => editor.remove_word(),
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => editor.select_word(None),
+            _ => {}
+        }
+        self.fit_layout(ctx);
+        Ok(())
+    }
+    // The board being edited with the words found on it, the cursor and the word list
+    fn draw_editor(&mut self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        let Some(editor) = &self.editor else {
+            return Ok(());
+        };
+        canvas.draw(&self.grid_mesh, graphics::DrawParam::new());
+        for (i, row) in editor.board.letters.iter().enumerate() {
+            for (j, letter) in row.iter().enumerate() {
+                canvas.draw(
+                    graphics::Text::new(*letter)
+                        .set_scale(self.layout.grid_size * 0.6)
+                        .set_font("Montserrat"),
+                    graphics::DrawParam::new()
+                        .dest(Vec2::from(self.layout.cell_center(i, j)))
+                        .color(graphics::Color::from(self.theme.text))
+                        .offset(Vec2::new(0.5, 0.5)),
+                );
+            }
+        }
+        draw_strikes(ctx, canvas, &self.layout, &editor.found, &self.theme)?;
+        if editor.selected_word.is_none() {
+            let (x, y) = self.layout.cell_origin(editor.cursor.0, editor.cursor.1);
+            let size = self.layout.grid_size;
+            let cursor = graphics::Mesh::new_rectangle(
+                ctx,
+                graphics::DrawMode::stroke(3.0),
+                graphics::Rect::new(x, y, size, size),
+                graphics::Color::from(self.theme.accent),
+            )?;
+            canvas.draw(&cursor, graphics::DrawParam::default());
+        }
+        // Words that are not on the board are in the error color, the one being typed in the accent
+        for (idx, word) in editor.words.iter().enumerate() {
+            let (text, color) = if editor.selected_word == Some(idx) {
+                (format!("{}_", word), self.theme.accent)
+            } else if editor.missing.contains(word) {
+                (word.clone(), self.theme.error)
+            } else {
+                (word.clone(), self.theme.text)
+            };
+            display_word(ctx, canvas, &self.layout, &text, idx, color.into());
+            // The empty word being typed is left out of the solved list, so match by word
+            if let Some(word_match) = editor.found.iter().find(|found| found.word == *word) {
+                let color = self.theme.word_color(word_match.index);
+                display_swatch(ctx, canvas, &self.layout, idx, color);
+            }
+        }
+        let status = format!(
+            "Editing {}x{}{}, {} of {} words on the board",
+            editor.board.get_rows(),
+            editor.board.get_cols(),
+            if editor.dirty { " (not saved)" } else { "" },
+            editor.words.len() - editor.missing.len(),
+            editor.words.len()
+        );
+        let keys = "[type] letter  [arrows] move  [ctrl+arrows] add/remove row or column  [tab] new word  [click] cell or word  [del] remove word  [ctrl+s] save  [f2] done";
+        self.draw_status(canvas, &format!("{}    {}", status, keys));
+        Ok(())
+    }
+    /// Start the search over on the current puzzle
+    pub fn restart(&mut self) {
+        self.strategy.restart();
+        let board = self.strategy.board();
+        self.trace = Trace::default();
+        self.heatmap = Heatmap::new(board.get_rows(), board.get_cols());
+        self.stats = SearchStats::new(board, &self.trie);
+        self.feasible = true;
+        self.found_words.clear();
+        self.current_idx = (Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0));
+        self.started = Instant::now();
+        self.finished_after = None;
+        self.seek_replay(0);
+        if self.game.is_some() {
+            self.start_game();
+        }
+    }
+    /// Switch to the next puzzle of `PUZZLES` and start searching it. The paths are relative to
+    /// the repository, run from anywhere else the current puzzle is kept.
+    pub fn load_next_puzzle(&mut self, ctx: &mut Context) {
+        self.puzzle_idx = (self.puzzle_idx + 1) % PUZZLES.len();
+        let (board_path, words_path) = PUZZLES[self.puzzle_idx];
+        if let Err(error) = self.open_puzzle(ctx, Path::new(board_path), Path::new(words_path)) {
+            eprintln!("Could not open the puzzle: {}", error);
+        }
+    }
+    /// Switch to the puzzle of the given files and start searching it, the current puzzle
+    /// is kept when they cannot be loaded
+    pub fn open_puzzle(
+        &mut self,
+        ctx: &mut Context,
+        board_path: &Path,
+        words_path: &Path,
+    ) -> Result<(), String> {
+        let (board_state, target_words) = load_puzzle(board_path, words_path)?;
+        self.set_puzzle(ctx, board_state, target_words);
+        self.puzzle_paths = Some((board_path.to_path_buf(), words_path.to_path_buf()));
+        println!("Opened {}", board_path.display());
+        Ok(())
+    }
+    // Rebuild the grid, the trie and the strategy for another puzzle, and start searching it
+    fn set_puzzle(&mut self, ctx: &mut Context, board_state: Board, target_words: Vec<String>) {
+        self.trie = Trie::from(&target_words.iter().map(String::as_str).collect());
+        self.strategy = by_name(
+            STRATEGY_NAMES[self.strategy_idx],
+            board_state,
+            self.trie.clone(),
+        )
+        .expect("every name of STRATEGY_NAMES should be known");
+        self.words = target_words;
+        // A trace only makes sense on the puzzle it was recorded on
+        self.replay = None;
+        self.restart();
+        self.fit_layout(ctx);
+    }
+    // Fit the layout to the window, the board and the length of the word list, the grid is
+    // only rebuilt when the layout changed
+    fn fit_layout(&mut self, ctx: &mut Context) {
+        let (rows, cols, word_count) = match &self.editor {
+            Some(editor) => (
+                editor.board.get_rows(),
+                editor.board.get_cols(),
+                editor.words.len(),
+            ),
+            None => {
+                let board = self.strategy.board();
+                let word_count = match &self.game {
+                    Some(game) => game.words.len(),
+                    None => usize::max(self.words.len(), self.found_words.len()),
+                };
+                (board.get_rows(), board.get_cols(), word_count)
+            }
+        };
+        let (width, height) = ctx.gfx.drawable_size();
+        let layout = Layout::fit(rows, cols, width, height, word_count);
+        if layout != self.layout {
+            self.layout = layout;
+            self.grid_mesh = build_grid(ctx, &self.layout, &self.theme);
+        }
+    }
+    /// Open the puzzle a directory or a board file stands for, or a board and a word list
+    pub fn open_paths(&mut self, ctx: &mut Context, paths: &[PathBuf]) -> Result<(), String> {
+        match paths {
+            [path] => {
+                let puzzle = puzzle_at(path).map_err(|error| error.to_string())?;
+                self.open_puzzle(ctx, &puzzle.board, &puzzle.words)
+            }
+            [first, second] => {
+                // The board is the file named like one, whatever order the files came in
+                let is_board = |path: &PathBuf| {
+                    path.file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with("board"))
+                };
+                let (board, words) = if !is_board(first) && is_board(second) {
+                    (second, first)
+                } else {
+                    (first, second)
+                };
+                self.open_puzzle(ctx, board, words)
+            }
+            _ => Err("Give a puzzle directory, a board file, or a board and a word list".into()),
+        }
+    }
+    /// Save where the search and the game are, a replay is not saved
+    pub fn session(&self) -> Session {
+        Session {
+            board: self.strategy.board().clone(),
+            words: self.words.clone(),
+            strategy: self.strategy.name().to_string(),
+            steps: self.strategy.steps(),
+            elapsed: self
+                .finished_after
+                .unwrap_or_else(|| self.started.elapsed()),
+            playback: self.playback,
+            game: self.game.as_ref().map(Game::progress),
+        }
+    }
+    /// Switch to the puzzle of a saved session, and run the search to the step it was saved at
+    pub fn resume(&mut self, ctx: &mut Context, session: Session) -> Result<(), String> {
+        let strategy_idx = STRATEGY_NAMES
+            .iter()
+            .position(|name| *name == session.strategy)
+            .ok_or_else(|| format!("Unknown strategy {}", session.strategy))?;
+        // Check the game before anything changes, so a broken session leaves the visualizer as it was
+        let game = match &session.game {
+            Some(progress) => Some(Game::resume(
+                session.board.clone(),
+                session.words.clone(),
+                progress,
+            )?),
+            None => None,
+        };
+        self.strategy_idx = strategy_idx;
+        self.stop_game();
+        self.editor = None;
+        self.set_puzzle(ctx, session.board, session.words);
+        // The files of the puzzle are not known, an edit is saved to `EDITED_PUZZLE`
+        self.puzzle_paths = None;
+        // The search is deterministic, running the saved steps again brings every counter back.
+        // The clock is not running meanwhile, it picks up from the saved time.
+        while self.strategy.steps() < session.steps {
+            if let Some(pos) = self.strategy.probe() {
+                self.current_idx = pos.to_vec2();
+            }
+            match self.strategy.step() {
+                StepEvent::Probe(step) => self.record(step),
+                StepEvent::Done => break,
+            }
+        }
+        self.started = Instant::now()
+            .checked_sub(session.elapsed)
+            .unwrap_or_else(Instant::now);
+        self.finished_after = self.strategy.is_done().then_some(session.elapsed);
+        self.playback = session.playback;
+        self.game = game;
+        Ok(())
+    }
+    /// Lines of the overlay shown once the search is finished
+    pub fn summary(&self) -> Vec<String> {
+        let missing = missing_words(&self.words, self.strategy.found());
+        let mut lines = vec![
+            "Search finished".to_string(),
+            format!(
+                "Words found: {} / {}",
+                self.words.len() - missing.len(),
+                self.words.len()
+            ),
+        ];
+        if !missing.is_empty() {
+            lines.push(format!("Missing: {}", missing.join(", ")));
+        }
+        lines.push(format!(
+            "Steps: {} with the {} strategy",
+            self.strategy.steps(),
+            self.strategy.name()
+        ));
+        if let Some(elapsed) = self.finished_after {
+            lines.push(format!("Elapsed: {:.1} s", elapsed.as_secs_f32()));
+        }
+        lines.push(
+            "[r] restart   [l] load another puzzle   [o] open a puzzle   [esc] quit".to_string(),
+        );
+        lines
+    }
 }
 
 impl EventHandler for MainState {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
         // Update code here...
-        const DESIRED_FPS: u32 = 30;
-        while ctx.time.check_update_time(DESIRED_FPS) {
-            if let Some(pos) = self.search_state.current_prefix() {
-                self.current_idx = pos.to_vec2();
-            }
-            if let Some(word_position) = self
-                .board_state
-                .check_state(&mut self.search_state, &self.trie)
-            {
-                println!("Found word: {:?}", self.search_state.current_prefix());
-                self.found_words_idx.push(word_position.to_1d(BOARD_SIZE));
+        if let Some((_, at)) = self.rejected {
+            if at.elapsed() >= REJECT_ANIMATION {
+                self.rejected = None;
             }
-            match self
-                .board_state
-                .next_state(&self.search_state, self.search_state.feasible)
+        }
+        // One step per tick, ticks keep being consumed while paused so resuming does not burst
+        while ctx.time.check_update_time(self.playback.steps_per_second) {
+            if self.playback.paused
+                || self.is_finished()
+                || self.game.is_some()
+                || self.editor.is_some()
             {
-                Some(state) => {
-                    self.search_state = state;
-                }
-                None => {
-                    // sleep
-
-                    std::thread::sleep(std::time::Duration::from_secs(20));
-                    ctx.request_quit();
-                }
+                continue;
             }
+            self.advance();
         }
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        // The word list may have grown a column since the last frame
+        self.fit_layout(ctx);
         let mut canvas =
-            graphics::Canvas::from_frame(ctx, graphics::Color::from([1.0, 1.0, 1.0, 1.0]));
+            graphics::Canvas::from_frame(ctx, graphics::Color::from(self.theme.background));
         // Draw code here...
+        if self.view.shows_heatmap() {
+            draw_heatmap(ctx, &mut canvas, &self.layout, &self.heatmap, &self.theme);
+        }
         canvas.draw(&self.grid_mesh, graphics::DrawParam::new());
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
+        if self.editor.is_some() {
+            self.draw_editor(ctx, &mut canvas)?;
+            canvas.finish(ctx)?;
+            return Ok(());
+        }
+        let board = self.strategy.board();
+        for i in 0..board.get_rows() {
+            for j in 0..board.get_cols() {
                 let text_dest = graphics::DrawParam::new()
-                    .dest(Vec2::new(
-                        START_X + GRID_SIZE * j as f32 + GRID_SIZE / 2.0,
-                        START_Y + GRID_SIZE * i as f32 + GRID_SIZE / 2.0,
-                    ))
-                    .color(graphics::Color::from([0.0, 0.0, 0.0, 1.0]))
+                    .dest(Vec2::from(self.layout.cell_center(i, j)))
+                    .color(graphics::Color::from(self.theme.text))
                     .offset(Vec2::new(0.5, 0.5));
-                canvas.draw(
-                    graphics::Text::new(self.board_state.letters[i][j])
-                        .set_scale(30.)
-                        .set_font("Montserrat"),
-                    text_dest,
-                );
+                // Visits over prunes, so the effort can be read without the colors
+                let text = if self.view == View::Heatmap {
+                    let heatmap = &self.heatmap;
+                    graphics::Text::new(format!(
+                        "{}/{}",
+                        heatmap.visits[i][j], heatmap.prunes[i][j]
+                    ))
+                    .set_scale(self.layout.grid_size * 0.28)
+                    .set_font("Montserrat")
+                    .clone()
+                } else {
+                    graphics::Text::new(board.letters[i][j])
+                        .set_scale(self.layout.grid_size * 0.6)
+                        .set_font("Montserrat")
+                        .clone()
+                };
+                canvas.draw(&text, text_dest);
             }
         }
-        let mut mb = self.mb.clone();
-        let found_words_idx = self.found_words_idx.clone();
-        // Construct the line mesh based on the found words
-        for (idx, word_idx) in found_words_idx.iter().enumerate() {
-            let start_idx = word_idx.0;
-            let end_idx = word_idx.1;
-            draw_line(self, ctx, start_idx, end_idx, &mut mb, &mut canvas)?;
-            let word = self
-                .board_state
-                .get_word_from_1d_position(start_idx, end_idx);
-            display_word(ctx, &mut canvas, &word, idx)
+        let found_words = match &self.game {
+            Some(game) => game.found.clone(),
+            None => self.found_words.clone(),
+        };
+        draw_strikes(ctx, &mut canvas, &self.layout, &found_words, &self.theme)?;
+        if self.game.is_some() {
+            self.draw_game(ctx, &mut canvas);
+            canvas.finish(ctx)?;
+            return Ok(());
+        }
+        // A replay lists every word of the trace, the ones not reached yet are greyed out
+        let listed_words = match &self.replay {
+            Some(replay) => &replay.matches,
+            None => &found_words,
+        };
+        for (idx, word_match) in listed_words.iter().enumerate() {
+            let color = if idx < found_words.len() {
+                let swatch = self.theme.word_color(word_match.index);
+                display_swatch(ctx, &mut canvas, &self.layout, idx, swatch);
+                self.theme.text
+            } else {
+                self.theme.muted
+            };
+            let (layout, word) = (&self.layout, &word_match.word);
+            display_word(ctx, &mut canvas, layout, word, idx, color.into())
         }
         // Draw the currently checking line
         draw_highlighted_line(
             ctx,
+            &self.layout,
             self.current_idx.0,
             self.current_idx.1,
             &mut canvas,
-            self.search_state.feasible,
+            self.feasible,
+            &self.theme,
         );
-        self.mb = mb;
+        if let Some(replay) = &self.replay {
+            display_timeline(
+                ctx,
+                &mut canvas,
+                &self.layout,
+                replay.position,
+                replay.len(),
+                &self.theme,
+            );
+        } else if self.strategy.is_done() {
+            display_summary(ctx, &mut canvas, &self.layout, &self.summary(), &self.theme);
+        }
+        let mut stats_lines = vec![format!("Strategy: {}", self.strategy.name())];
+        stats_lines.extend(self.stats.lines());
+        stats_lines.push(if self.playback.paused {
+            "Target speed: paused".to_string()
+        } else {
+            format!("Target speed: {} steps/s", self.playback.steps_per_second)
+        });
+        stats_lines.push(format!("Theme: {}", self.theme.name));
+        display_stats_panel(&mut canvas, &self.layout, &stats_lines, &self.theme);
+        // Playback status under the grid
+        let status = if let Some(replay) = &self.replay {
+            format!("Replay step {} / {}", replay.position, replay.len())
+        } else if self.strategy.is_done() {
+            "Finished".to_string()
+        } else if self.playback.paused {
+            format!("Paused at step {}", self.strategy.steps())
+        } else {
+            format!("Running, target {} steps/s", self.playback.steps_per_second)
+        };
+        let keys = if self.replay.is_some() {
+            "[space] pause  [left/right] step  [home/end] jump  click a word to jump to it"
+        } else {
+            "[space] pause  [right] step  [+/-] speed  [end] skip to end  [t] save trace  [v] view  [s] strategy  [c] theme  [f2] edit"
+        };
+        self.draw_status(&mut canvas, &format!("{}    {}", status, keys));
         canvas.finish(ctx)?;
         Ok(())
     }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        input: KeyInput,
+        _repeated: bool,
+    ) -> GameResult {
+        if self.editor.is_some() {
+            return self.editor_key_down(ctx, input);
+        }
+        // Every key goes to the path being typed, letters come through `text_input_event`
+        if let Some(path) = self.prompt.as_mut() {
+            match input.keycode {
+                Some(KeyCode::Escape) => self.prompt = None,
+                Some(KeyCode::Back) => {
+                    path.pop();
+                }
+                Some(KeyCode::Return | KeyCode::NumpadEnter) => {
+                    let paths: Vec<PathBuf> = path.split_whitespace().map(PathBuf::from).collect();
+                    self.prompt = None;
+                    if let Err(error) = self.open_paths(ctx, &paths) {
+                        eprintln!("Could not open the puzzle: {}", error);
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+        let confirmed = std::mem::take(&mut self.confirm_new_game);
+        match input.keycode {
+            Some(KeyCode::Escape) => ctx.request_quit(),
+            Some(KeyCode::Space) => self.playback.toggle_pause(),
+            // Single step pauses the animation so the step can be looked at
+            Some(KeyCode::Right) if self.game.is_none() => {
+                self.playback.paused = true;
+                self.advance();
+            }
+            Some(KeyCode::Plus | KeyCode::Equals | KeyCode::NumpadAdd) => self.playback.faster(),
+            Some(KeyCode::Minus | KeyCode::NumpadSubtract) => self.playback.slower(),
+            Some(KeyCode::Left) if self.replay.is_some() => {
+                self.playback.paused = true;
+                let position = self.replay.as_ref().map(|r| r.position.saturating_sub(1));
+                if let Some(position) = position {
+                    self.seek_replay(position);
+                }
+            }
+            Some(KeyCode::Home) => self.seek_replay(0),
+            Some(KeyCode::End) if self.game.is_none() => {
+                while !self.is_finished() {
+                    self.advance();
+                }
+            }
+            // Save the steps run so far, to be replayed with the `replay` command
+            Some(KeyCode::T) if self.game.is_none() => {
+                let path = Path::new("trace.txt");
+                self.trace.save(path)?;
+                println!("Trace saved to {}", path.display());
+            }
+            Some(KeyCode::F5) => {
+                self.session().save(Path::new(SESSION_PATH))?;
+                println!("Session saved to {}", SESSION_PATH);
+            }
+            // A session that cannot be loaded leaves the current one running
+            Some(KeyCode::F9) => match Session::load(Path::new(SESSION_PATH)) {
+                Ok(session) => match self.resume(ctx, session) {
+                    Ok(()) => println!("Session loaded from {}", SESSION_PATH),
+                    Err(error) => eprintln!("Could not resume {}: {}", SESSION_PATH, error),
+                },
+                Err(error) => eprintln!("Could not load {}: {}", SESSION_PATH, error),
+            },
+            // A new game loses the words found so far, the key has to be pressed twice
+            Some(KeyCode::R) => match &self.game {
+                Some(game) if !game.found.is_empty() && !game.is_finished() && !confirmed => {
+                    self.confirm_new_game = true
+                }
+                _ => self.restart(),
+            },
+            Some(KeyCode::L) => self.load_next_puzzle(ctx),
+            Some(KeyCode::O) => {
+                self.prompt = Some(String::new());
+                self.prompt_opening = true;
+            }
+            Some(KeyCode::F2) => self.start_editing(),
+            Some(KeyCode::S) if self.game.is_none() => self.next_strategy(),
+            Some(KeyCode::C) => self.next_theme(ctx),
+            Some(KeyCode::V) => self.view = self.view.next(),
+            Some(KeyCode::H) => {
+                if let Some(game) = self.game.as_mut() {
+                    game.hint();
+                }
+            }
+            Some(KeyCode::P) => {
+                if self.game.is_some() {
+                    self.stop_game();
+                } else {
+                    self.start_game();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn resize_event(&mut self, ctx: &mut Context, _width: f32, _height: f32) -> GameResult {
+        self.fit_layout(ctx);
+        Ok(())
+    }
+
+    /// Save the session on the way out, so closing the window does not lose the progress
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        match self.session().save(Path::new(SESSION_PATH)) {
+            Ok(()) => println!("Session saved to {}", SESSION_PATH),
+            Err(error) => eprintln!("Could not save {}: {}", SESSION_PATH, error),
+        }
+        Ok(false)
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) -> GameResult {
+        if let Some(editor) = self.editor.as_mut() {
+            editor.type_letter(character);
+        }
+        if let Some(path) = self.prompt.as_mut() {
+            // The key that opened the prompt also types its letter, it is not part of the path
+            let open_key =
+                std::mem::take(&mut self.prompt_opening) && character.eq_ignore_ascii_case(&'o');
+            if !character.is_control() && !open_key {
+                path.push(character);
+            }
+        }
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        if button != MouseButton::Left {
+            return Ok(());
+        }
+        if let Some(editor) = self.editor.as_mut() {
+            if let Some(cell) = self.layout.cell_at(x, y) {
+                editor.click_cell(cell);
+            } else if let Some(idx) = self.layout.word_list_index_at(x, y) {
+                editor.select_word(Some(idx));
+            }
+            return Ok(());
+        }
+        if self.game.as_ref().is_some_and(|game| !game.is_finished()) {
+            self.selection = self.layout.cell_at(x, y).map(|cell| (cell, cell));
+            return Ok(());
+        }
+        if self.replay.is_none() {
+            return Ok(());
+        }
+        let (timeline_x, timeline_y, width, height) = self.layout.timeline_rect();
+        if x >= timeline_x && x <= timeline_x + width && y >= timeline_y && y <= timeline_y + height
+        {
+            self.dragging_timeline = true;
+            self.seek_timeline(x);
+        } else if let Some(idx) = self.layout.word_list_index_at(x, y) {
+            // Jump to the step that found the clicked word
+            if let Some(replay) = self.replay.as_mut() {
+                if let Some(word_position) = replay.matches.get(idx).map(Match::position) {
+                    replay.jump_to(&word_position);
+                    self.playback.paused = true;
+                    self.sync_replay();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        _button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> GameResult {
+        self.dragging_timeline = false;
+        self.end_selection();
+        Ok(())
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> GameResult {
+        if self.dragging_timeline {
+            self.seek_timeline(x);
+        }
+        // Outside the grid the selection keeps its last cell
+        if let (Some((start, _)), Some(cell)) = (self.selection, self.layout.cell_at(x, y)) {
+            self.selection = Some((start, cell));
+        }
+        Ok(())
+    }
+}
+
+/// A directory or a board file opens its puzzle at once. A file that is not a puzzle on its own
+/// waits for the next one, and the two are opened as a board and its word list.
+impl FileDropHandler for MainState {
+    fn file_dropped_event(&mut self, ctx: &mut Context, path: PathBuf) -> GameResult {
+        if self.dropped.is_empty() && puzzle_at(&path).is_ok() {
+            if let Err(error) = self.open_paths(ctx, &[path]) {
+                eprintln!("Could not open the puzzle: {}", error);
+            }
+            return Ok(());
+        }
+        self.dropped.push(path);
+        if self.dropped.len() == 2 {
+            let dropped = std::mem::take(&mut self.dropped);
+            if let Err(error) = self.open_paths(ctx, &dropped) {
+                eprintln!("Could not open the puzzle: {}", error);
+            }
+        } else {
+            println!("Drop the other file of the puzzle to open it");
+        }
+        Ok(())
+    }
 }
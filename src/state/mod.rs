@@ -1,2 +1,7 @@
+#[cfg(feature = "ggez")]
+pub mod compare_state;
+#[cfg(feature = "ggez")]
+pub mod event_loop;
+#[cfg(feature = "ggez")]
 pub mod main_state;
 pub mod search_state;
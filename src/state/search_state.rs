@@ -1,6 +1,9 @@
 use crate::board::*;
 use crate::constant::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SearchState {
     pub position: (usize, usize),
     pub direction: Direction,
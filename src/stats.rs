@@ -0,0 +1,140 @@
+use crate::board::{Board, Direction};
+use crate::trace::TraceStep;
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Counters of a search, kept up to date step by step by the visualizers and returned by the batch solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchStats {
+    /// Cell, direction and distance of the last probe
+    pub position: (usize, usize),
+    pub direction: Direction,
+    pub distance: i32,
+    pub steps: usize,
+    /// Probes no target word starts with
+    pub prunes: usize,
+    pub words_found: usize,
+    /// Distinct target words
+    pub target_words: usize,
+    rows: usize,
+    cols: usize,
+}
+impl SearchStats {
+    pub fn new(board: &Board, trie: &Trie) -> Self {
+        SearchStats {
+            target_words: trie.word_count(),
+            rows: board.get_rows(),
+            cols: board.get_cols(),
+            ..Default::default()
+        }
+    }
+    /// Compute the stats of the given steps, used to catch up on a replay
+    pub fn from_steps(board: &Board, trie: &Trie, steps: &[TraceStep]) -> Self {
+        let mut stats = SearchStats::new(board, trie);
+        for step in steps {
+            stats.record(board, trie, step);
+        }
+        stats
+    }
+    /// Count one step of the search
+    pub fn record(&mut self, board: &Board, trie: &Trie, step: &TraceStep) {
+        self.position = step.position;
+        self.direction = step.direction;
+        self.distance = step.distance;
+        self.steps += 1;
+        if step.is_pruned(board, trie) {
+            self.prunes += 1;
+        }
+        if step.found.is_some() {
+            self.words_found += 1;
+        }
+    }
+    /// Guess the steps left from the average steps per cell so far, None before the first cell is done
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::solver::solve_with_stats;
+    /// use word_search_solver::stats::SearchStats;
+    /// use word_search_solver::trie::Trie;
+    /// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+    /// let trie = Trie::from(&vec!["ab"]);
+    /// assert_eq!(SearchStats::new(&board, &trie).estimated_remaining_steps(), None);
+    /// let (_, stats) = solve_with_stats(&board, &trie);
+    /// assert_eq!(stats.estimated_remaining_steps(), Some(0));
+    /// ```
+    pub fn estimated_remaining_steps(&self) -> Option<usize> {
+        let cells = self.rows * self.cols;
+        // Cells before the current one are done, the last cell is done once its last direction is
+        let done = self.position.0 * self.cols + self.position.1;
+        if self.steps == 0 {
+            return None;
+        }
+        if done + 1 == cells && self.direction.next().is_none() {
+            return Some(0);
+        }
+        if done == 0 {
+            return None;
+        }
+        Some(self.steps * (cells - done) / done)
+    }
+    /// Describe the stats one per line, for the side panels
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Cell: {:?}", self.position),
+            format!("Direction: {}", self.direction.name()),
+            format!("Distance: {}", self.distance),
+            format!("Steps: {}", self.steps),
+            format!("Pruned: {}", self.prunes),
+            format!("Words: {} / {}", self.words_found, self.target_words),
+        ];
+        match self.estimated_remaining_steps() {
+            Some(remaining) => lines.push(format!("Remaining: ~{} steps", remaining)),
+            None => lines.push("Remaining: unknown".to_string()),
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{solve_with_stats, Searcher};
+    use crate::trace::Trace;
+
+    #[test]
+    fn test_stats_match_searcher() {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let words = vec!["abc", "aei", "xyz"];
+        let trie = Trie::from(&words);
+        let mut searcher = Searcher::new(board.clone(), trie.clone());
+        let mut stats = SearchStats::new(&board, &trie);
+        for step in (0..40).filter_map(|_| searcher.step()) {
+            stats.record(&board, &trie, &step);
+        }
+        assert_eq!(stats.steps, 40);
+        assert_eq!(stats.target_words, 3);
+        assert_eq!(stats.words_found, searcher.found.len());
+        let remaining = stats.estimated_remaining_steps().unwrap();
+
+        while let Some(step) = searcher.step() {
+            stats.record(&board, &trie, &step);
+        }
+        let total = stats.steps;
+        // The estimate is based on the cells done, it is in the right ballpark
+        assert!(remaining > (total - 40) / 2 && remaining < (total - 40) * 2);
+        assert_eq!(stats.words_found, 2);
+        assert!(stats.prunes > 0);
+        assert_eq!(stats.estimated_remaining_steps(), Some(0));
+        let trace = Trace::record(board.clone(), trie.clone());
+        assert_eq!(SearchStats::from_steps(&board, &trie, &trace.steps), stats);
+        let (found, batch_stats) = solve_with_stats(&board, &trie);
+        assert_eq!(found, searcher.found);
+        assert_eq!(batch_stats, stats);
+    }
+}
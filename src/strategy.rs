@@ -0,0 +1,236 @@
+use crate::board::{Board, WordPosition};
+use crate::line_scan::LineScan;
+use crate::matches::Match;
+use crate::solver::Searcher;
+use crate::state::search_state::SearchState;
+use crate::trace::TraceStep;
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Names accepted by `by_name`, in the order the visualizer cycles through them
+pub const STRATEGY_NAMES: [&str; 3] = ["trie", "brute-force", "aho-corasick"];
+
+/// What one step of a strategy did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StepEvent {
+    /// A probe was checked, its `found` is set when it spelled a target word
+    Probe(TraceStep),
+    /// There was nothing left to check
+    Done,
+}
+
+/// A search algorithm that can be driven one step at a time, by the visualizers or to completion
+pub trait SearchStrategy {
+    /// Short name shown in the panes and accepted on the command line
+    fn name(&self) -> &'static str;
+    fn board(&self) -> &Board;
+    fn step(&mut self) -> StepEvent;
+    fn is_done(&self) -> bool;
+    /// Words found so far, in the order they were found
+    fn found(&self) -> &[Match];
+    /// Number of probes checked so far
+    fn steps(&self) -> usize;
+    /// The line the next step will check, if any
+    fn probe(&self) -> Option<WordPosition>;
+    /// Start over on the same board
+    fn restart(&mut self);
+    /// Run the remaining steps at once
+    fn finish(&mut self) {
+        while !self.is_done() {
+            self.step();
+        }
+    }
+}
+
+/// Create a strategy from its name
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::strategy::{by_name, STRATEGY_NAMES};
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+/// for name in STRATEGY_NAMES {
+///     let mut strategy = by_name(name, board.clone(), Trie::from(&vec!["ab", "db"])).unwrap();
+///     assert_eq!(strategy.name(), name);
+///     strategy.finish();
+///     assert_eq!(strategy.found().len(), 2);
+/// }
+/// assert!(by_name("bogo", board, Trie::new()).is_none());
+/// ```
+pub fn by_name(name: &str, board: Board, trie: Trie) -> Option<Box<dyn SearchStrategy>> {
+    match name {
+        "trie" => Some(Box::new(Searcher::new(board, trie))),
+        "brute-force" => Some(Box::new(BruteForce::new(board, trie))),
+        "aho-corasick" => Some(Box::new(LineScan::new(board, trie))),
+        _ => None,
+    }
+}
+
+/// The stepping engine prunes every probe that no target word starts with
+impl SearchStrategy for Searcher {
+    fn name(&self) -> &'static str {
+        "trie"
+    }
+    fn board(&self) -> &Board {
+        &self.board
+    }
+    fn step(&mut self) -> StepEvent {
+        match Searcher::step(self) {
+            Some(step) => StepEvent::Probe(step),
+            None => StepEvent::Done,
+        }
+    }
+    fn is_done(&self) -> bool {
+        self.is_finished()
+    }
+    fn found(&self) -> &[Match] {
+        &self.found
+    }
+    fn steps(&self) -> usize {
+        self.steps
+    }
+    fn probe(&self) -> Option<WordPosition> {
+        Searcher::probe(self)
+    }
+    fn restart(&mut self) {
+        Searcher::restart(self)
+    }
+}
+
+/// Check every line from every cell in every direction up to the edge of the board, without pruning.
+/// It finds the same words as the trie search, in the same order, with many more probes.
+pub struct BruteForce {
+    board: Board,
+    trie: Trie,
+    state: SearchState,
+    found: Vec<Match>,
+    steps: usize,
+    done: bool,
+}
+impl BruteForce {
+    pub fn new(board: Board, trie: Trie) -> Self {
+        BruteForce {
+            board,
+            trie,
+            state: SearchState::new(),
+            found: Vec::new(),
+            steps: 0,
+            done: false,
+        }
+    }
+}
+impl SearchStrategy for BruteForce {
+    fn name(&self) -> &'static str {
+        "brute-force"
+    }
+    fn board(&self) -> &Board {
+        &self.board
+    }
+    fn step(&mut self) -> StepEvent {
+        if self.done {
+            return StepEvent::Done;
+        }
+        self.steps += 1;
+        let (i, j) = self.state.position;
+        let (direction, distance) = (self.state.direction, self.state.distance);
+        let found = Match::on_board(&self.board, &self.trie, (i, j), direction, distance);
+        let word_position = found.as_ref().map(Match::position);
+        self.found.extend(found);
+        // Keep going until the line leaves the board
+        let feasible = self
+            .board
+            .get_string_from_direction(i, j, &direction, distance + 1)
+            .is_some();
+        let step = TraceStep {
+            position: (i, j),
+            direction,
+            distance,
+            feasible,
+            found: word_position,
+        };
+        match self.board.next_state(&self.state, feasible) {
+            Some(state) => self.state = state,
+            None => self.done = true,
+        }
+        StepEvent::Probe(step)
+    }
+    fn is_done(&self) -> bool {
+        self.done
+    }
+    fn found(&self) -> &[Match] {
+        &self.found
+    }
+    fn steps(&self) -> usize {
+        self.steps
+    }
+    fn probe(&self) -> Option<WordPosition> {
+        if self.done {
+            return None;
+        }
+        self.state.current_prefix()
+    }
+    fn restart(&mut self) {
+        self.state = SearchState::new();
+        self.found.clear();
+        self.steps = 0;
+        self.done = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::solve;
+    use crate::trace::Trace;
+
+    fn board() -> Board {
+        Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ])
+    }
+    #[test]
+    fn test_strategies_find_the_same_words() {
+        let words = vec!["abc", "aei", "fc", "hd", "ihg"];
+        let expected = solve(&board(), &Trie::from(&words));
+        let mut strategies: Vec<Box<dyn SearchStrategy>> = vec![
+            Box::new(Searcher::new(board(), Trie::from(&words))),
+            Box::new(BruteForce::new(board(), Trie::from(&words))),
+            Box::new(LineScan::new(board(), Trie::from(&words))),
+        ];
+        for strategy in strategies.iter_mut() {
+            strategy.finish();
+            // The line scan finds the words line by line, in another order
+            let mut found = strategy.found().to_vec();
+            found.sort();
+            assert_eq!(found, expected, "{}", strategy.name());
+            assert_eq!(strategy.step(), StepEvent::Done);
+            assert_eq!(strategy.probe(), None);
+        }
+        // Pruning is the whole point of the trie
+        assert!(strategies[0].steps() < strategies[1].steps());
+        // Single letters in 8 directions, then 9 longer lines per row or column direction
+        // and 5 per diagonal direction
+        assert_eq!(strategies[1].steps(), 9 * 8 + 4 * 9 + 4 * 5);
+        // One step per line: 3 rows or columns per straight direction, 5 lines per diagonal one
+        assert_eq!(strategies[2].steps(), 4 * 3 + 4 * 5);
+    }
+    #[test]
+    fn test_step_events_match_the_recorded_trace() {
+        let mut searcher = Searcher::new(board(), Trie::from(&vec!["abc"]));
+        let mut events = Vec::new();
+        while let StepEvent::Probe(step) = SearchStrategy::step(&mut searcher) {
+            events.push(step);
+        }
+        assert_eq!(
+            events,
+            Trace::record(board(), Trie::from(&vec!["abc"])).steps
+        );
+        searcher.restart();
+        assert_eq!(SearchStrategy::steps(&searcher), 0);
+        assert!(!searcher.is_done());
+    }
+}
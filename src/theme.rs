@@ -0,0 +1,232 @@
+use crate::heatmap::heat_color;
+
+/// A color as RGBA from 0 to 1, like `heat_color`
+pub type Rgba = [f32; 4];
+
+/// Named color palette of the visualizers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub background: Rgba,
+    /// Letters and words
+    pub text: Rgba,
+    /// Greyed out words, and the track of the timeline
+    pub muted: Rgba,
+    /// Stats panel and status line
+    pub panel: Rgba,
+    /// Border of the grid
+    pub border: Rgba,
+    /// Line of the probe being checked
+    pub probe: Rgba,
+    /// Circle of a probe on a single cell
+    pub probe_cell: Rgba,
+    /// Hints, the edit cursor, the word being typed and the timeline
+    pub accent: Rgba,
+    /// Wrong selections and words that are not on the board
+    pub error: Rgba,
+    /// One color per found word, shaded toward the background when there are more words than colors
+    pub word_colors: &'static [Rgba],
+}
+
+pub const LIGHT: Theme = Theme {
+    name: "light",
+    background: [1.0, 1.0, 1.0, 1.0],
+    text: [0.0, 0.0, 0.0, 1.0],
+    muted: [0.65, 0.65, 0.65, 1.0],
+    panel: [0.25, 0.25, 0.25, 1.0],
+    border: [1.0, 0.0, 0.0, 1.0],
+    probe: [0.0, 0.8, 0.0, 1.0],
+    probe_cell: [1.0, 0.0, 0.0, 0.5],
+    accent: [1.0, 0.5, 0.0, 1.0],
+    error: [0.9, 0.0, 0.0, 1.0],
+    word_colors: &[
+        [0.12, 0.47, 0.71, 1.0],
+        [1.0, 0.5, 0.05, 1.0],
+        [0.17, 0.63, 0.17, 1.0],
+        [0.84, 0.15, 0.16, 1.0],
+        [0.58, 0.4, 0.74, 1.0],
+        [0.55, 0.34, 0.29, 1.0],
+        [0.89, 0.47, 0.76, 1.0],
+        [0.74, 0.74, 0.13, 1.0],
+        [0.09, 0.75, 0.81, 1.0],
+        [0.5, 0.5, 0.5, 1.0],
+    ],
+};
+
+pub const DARK: Theme = Theme {
+    name: "dark",
+    background: [0.12, 0.12, 0.14, 1.0],
+    text: [0.92, 0.92, 0.92, 1.0],
+    muted: [0.45, 0.45, 0.48, 1.0],
+    panel: [0.75, 0.75, 0.78, 1.0],
+    border: [0.9, 0.35, 0.35, 1.0],
+    probe: [0.35, 0.9, 0.45, 1.0],
+    probe_cell: [1.0, 0.35, 0.35, 0.5],
+    accent: [1.0, 0.65, 0.25, 1.0],
+    error: [1.0, 0.4, 0.4, 1.0],
+    word_colors: &[
+        [0.4, 0.65, 1.0, 1.0],
+        [1.0, 0.7, 0.3, 1.0],
+        [0.45, 0.85, 0.45, 1.0],
+        [1.0, 0.45, 0.5, 1.0],
+        [0.75, 0.6, 1.0, 1.0],
+        [0.3, 0.85, 0.85, 1.0],
+        [1.0, 0.6, 0.85, 1.0],
+        [0.9, 0.9, 0.4, 1.0],
+    ],
+};
+
+pub const HIGH_CONTRAST: Theme = Theme {
+    name: "high-contrast",
+    background: [0.0, 0.0, 0.0, 1.0],
+    text: [1.0, 1.0, 1.0, 1.0],
+    muted: [0.7, 0.7, 0.7, 1.0],
+    panel: [1.0, 1.0, 1.0, 1.0],
+    border: [1.0, 1.0, 0.0, 1.0],
+    probe: [0.0, 1.0, 0.0, 1.0],
+    probe_cell: [1.0, 0.0, 1.0, 0.6],
+    accent: [1.0, 1.0, 0.0, 1.0],
+    error: [1.0, 0.2, 0.2, 1.0],
+    // Yellow and green are left to the border, the accent and the probe
+    word_colors: &[
+        [0.0, 1.0, 1.0, 1.0],
+        [1.0, 0.0, 1.0, 1.0],
+        [1.0, 0.5, 0.0, 1.0],
+        [0.3, 0.5, 1.0, 1.0],
+        [1.0, 0.6, 0.75, 1.0],
+    ],
+};
+
+/// Okabe-Ito palette, told apart with any kind of color blindness. Blue, orange and vermillion
+/// mark the probe, the accent and the errors, the words get the other four colors.
+pub const COLORBLIND: Theme = Theme {
+    name: "colorblind",
+    background: [1.0, 1.0, 1.0, 1.0],
+    text: [0.0, 0.0, 0.0, 1.0],
+    muted: [0.6, 0.6, 0.6, 1.0],
+    panel: [0.25, 0.25, 0.25, 1.0],
+    border: [0.0, 0.45, 0.7, 1.0],
+    probe: [0.0, 0.45, 0.7, 1.0],
+    probe_cell: [0.84, 0.37, 0.0, 0.5],
+    accent: [0.9, 0.6, 0.0, 1.0],
+    error: [0.84, 0.37, 0.0, 1.0],
+    word_colors: &[
+        [0.34, 0.71, 0.91, 1.0],
+        [0.0, 0.62, 0.45, 1.0],
+        [0.94, 0.89, 0.26, 1.0],
+        [0.8, 0.47, 0.65, 1.0],
+    ],
+};
+
+/// Every theme, the theme key cycles through them in this order
+pub const THEMES: [Theme; 4] = [LIGHT, DARK, HIGH_CONTRAST, COLORBLIND];
+
+impl Default for Theme {
+    fn default() -> Self {
+        LIGHT
+    }
+}
+impl Theme {
+    /// Get a theme from its name
+    /// # Example
+    /// ```
+    /// use word_search_solver::theme::{Theme, DARK};
+    /// assert_eq!(Theme::by_name("dark"), Some(DARK));
+    /// assert_eq!(Theme::by_name("sepia"), None);
+    /// ```
+    pub fn by_name(name: &str) -> Option<Theme> {
+        THEMES.iter().find(|theme| theme.name == name).copied()
+    }
+    /// Get the theme after this one in `THEMES`, back to the first after the last
+    /// # Example
+    /// ```
+    /// use word_search_solver::theme::{COLORBLIND, DARK, LIGHT};
+    /// assert_eq!(LIGHT.next(), DARK);
+    /// assert_eq!(COLORBLIND.next(), LIGHT);
+    /// ```
+    pub fn next(&self) -> Theme {
+        let idx = THEMES
+            .iter()
+            .position(|theme| theme.name == self.name)
+            .unwrap_or(0);
+        THEMES[(idx + 1) % THEMES.len()]
+    }
+    /// Get the color of the word at `idx` in the word list, the same on the grid and in the list.
+    /// Past the end of the palette the colors come back closer to the background each time
+    /// around, so that no two words of a long list look the same.
+    /// # Example
+    /// ```
+    /// use word_search_solver::theme::LIGHT;
+    /// assert_ne!(LIGHT.word_color(0), LIGHT.word_color(1));
+    /// let len = LIGHT.word_colors.len();
+    /// assert_ne!(LIGHT.word_color(0), LIGHT.word_color(len));
+    /// assert_eq!(LIGHT.word_color(len + 1)[3], 1.0);
+    /// ```
+    pub fn word_color(&self, idx: usize) -> Rgba {
+        let color = self.word_colors[idx % self.word_colors.len()];
+        let round = (idx / self.word_colors.len()) as i32;
+        let t = 1.0 - 0.6f32.powi(round);
+        let mut shaded = color;
+        for (channel, value) in shaded.iter_mut().enumerate().take(3) {
+            *value = color[channel] * (1.0 - t) + self.background[channel] * t;
+        }
+        shaded
+    }
+    /// Color of a heatmap cell: the background when cold, then `heat_color` from yellow to red
+    /// # Example
+    /// ```
+    /// use word_search_solver::theme::{DARK, LIGHT};
+    /// assert_eq!(DARK.heat_color(0.0), DARK.background);
+    /// assert_eq!(DARK.heat_color(1.0), [1.0, 0.0, 0.0, 1.0]);
+    /// assert_eq!(LIGHT.heat_color(0.25), [1.0, 1.0, 0.75, 1.0]);
+    /// ```
+    pub fn heat_color(&self, intensity: f32) -> Rgba {
+        let heat = heat_color(intensity);
+        let t = (2.0 * intensity).clamp(0.0, 1.0);
+        let mut color = [0.0; 4];
+        for (channel, value) in color.iter_mut().enumerate() {
+            *value = self.background[channel] * (1.0 - t) + heat[channel] * t;
+        }
+        color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_themes_are_distinct() {
+        for (idx, theme) in THEMES.iter().enumerate() {
+            assert_eq!(Theme::by_name(theme.name), Some(*theme));
+            assert_eq!(THEMES[(idx + 1) % THEMES.len()], theme.next());
+            assert_ne!(theme.text, theme.background);
+            // No two words share a color until the palette wraps around, and a struck word
+            // cannot be mistaken for the probe, the grid or a hint
+            let ui_colors = [
+                theme.background,
+                theme.probe,
+                theme.accent,
+                theme.error,
+                theme.border,
+            ];
+            for (i, color) in theme.word_colors.iter().enumerate() {
+                assert!(!theme.word_colors[i + 1..].contains(color));
+                assert!(!ui_colors.contains(color), "{} {:?}", theme.name, color);
+            }
+            // Longer lists, like the twelve months, still get a color of their own per word
+            let colors: Vec<Rgba> = (0..3 * theme.word_colors.len().max(12))
+                .map(|idx| theme.word_color(idx))
+                .collect();
+            for (i, color) in colors.iter().enumerate() {
+                assert!(
+                    !colors[i + 1..].contains(color),
+                    "{} word {}",
+                    theme.name,
+                    i
+                );
+                assert!(!ui_colors.contains(color), "{} {:?}", theme.name, color);
+            }
+        }
+    }
+}
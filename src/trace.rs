@@ -0,0 +1,335 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::matches::Match;
+use crate::solver::Searcher;
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const HEADER: &str = "# word search trace v1";
+
+/// What the stepping engine did in one step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TraceStep {
+    pub position: (usize, usize),
+    pub direction: Direction,
+    pub distance: i32,
+    /// Whether the checked prefix can still lead to a word
+    pub feasible: bool,
+    pub found: Option<WordPosition>,
+}
+impl TraceStep {
+    /// Write the step as one line: `row col direction distance feasible [start_row start_col end_row end_col]`
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Direction, WordPosition};
+    /// use word_search_solver::trace::TraceStep;
+    /// let step = TraceStep {
+    ///     position: (0, 1),
+    ///     direction: Direction::DownLeft,
+    ///     distance: 1,
+    ///     feasible: true,
+    ///     found: Some(WordPosition::new((0, 1), (1, 0))),
+    /// };
+    /// assert_eq!(step.to_line(), "0 1 down-left 1 true 0 1 1 0");
+    /// assert_eq!(TraceStep::from_line(&step.to_line()), Some(step));
+    /// ```
+    pub fn to_line(&self) -> String {
+        let mut line = format!(
+            "{} {} {} {} {}",
+            self.position.0,
+            self.position.1,
+            self.direction.name(),
+            self.distance,
+            self.feasible
+        );
+        if let Some(found) = self.found {
+            let (start, end) = (found.start(), found.end());
+            line.push_str(&format!(" {} {} {} {}", start.0, start.1, end.0, end.1));
+        }
+        line
+    }
+    /// Whether the probe was pruned: it is on the board but no target word starts with it
+    pub fn is_pruned(&self, board: &Board, trie: &Trie) -> bool {
+        let (i, j) = self.position;
+        board
+            .get_string_from_direction(i, j, &self.direction, self.distance)
+            .is_some_and(|prefix| !trie.starts_with(&prefix))
+    }
+    /// Parse a line written by `to_line`, return None if it is malformed
+    pub fn from_line(line: &str) -> Option<TraceStep> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 5 && fields.len() != 9 {
+            return None;
+        }
+        let found = if fields.len() == 9 {
+            let numbers: Vec<usize> = fields[5..]
+                .iter()
+                .map(|field| field.parse().ok())
+                .collect::<Option<_>>()?;
+            Some(WordPosition::new(
+                (numbers[0], numbers[1]),
+                (numbers[2], numbers[3]),
+            ))
+        } else {
+            None
+        };
+        Some(TraceStep {
+            position: (fields[0].parse().ok()?, fields[1].parse().ok()?),
+            direction: Direction::from_name(fields[2])?,
+            distance: fields[3].parse().ok()?,
+            feasible: fields[4].parse().ok()?,
+            found,
+        })
+    }
+}
+
+/// Every step of a search, in order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+}
+impl Trace {
+    /// Run the whole search and record it
+    pub fn record(board: Board, trie: Trie) -> Trace {
+        let mut searcher = Searcher::new(board, trie);
+        let steps = std::iter::from_fn(|| searcher.step()).collect();
+        Trace { steps }
+    }
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut contents = String::from(HEADER);
+        contents.push('\n');
+        for step in self.steps.iter() {
+            contents.push_str(&step.to_line());
+            contents.push('\n');
+        }
+        fs::write(path, contents)
+    }
+    pub fn load(path: &Path) -> Result<Trace, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let mut steps = Vec::new();
+        for (idx, line) in contents.lines().enumerate() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let step = TraceStep::from_line(line)
+                .ok_or_else(|| format!("Invalid trace step on line {}: {}", idx + 1, line))?;
+            steps.push(step);
+        }
+        Ok(Trace { steps })
+    }
+    /// Check that the probe of every step and every found word are on the board, so a trace
+    /// recorded on another puzzle or edited by hand is rejected instead of replayed.
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::trace::{Trace, TraceStep};
+    /// use word_search_solver::trie::Trie;
+    /// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+    /// let mut trace = Trace::record(board.clone(), Trie::from(&vec!["ab"]));
+    /// assert_eq!(trace.validate(&board), Ok(()));
+    /// trace.steps.push(TraceStep::from_line("2 0 up 1 true").unwrap());
+    /// let error = format!("Trace step {} is off the board: 2 0 up 1 true", trace.steps.len());
+    /// assert_eq!(trace.validate(&board), Err(error));
+    /// ```
+    pub fn validate(&self, board: &Board) -> Result<(), String> {
+        let on_board = |(i, j): (usize, usize)| i < board.get_rows() && j < board.get_cols();
+        for (idx, step) in self.steps.iter().enumerate() {
+            let (i, j) = step.position;
+            let end = Board::get_pos_from_direction(i, j, &step.direction, step.distance);
+            let probe_on_board =
+                step.distance >= 0 && on_board(step.position) && end.is_some_and(on_board);
+            let found_on_board = step
+                .found
+                .is_none_or(|found| on_board(found.start()) && on_board(found.end()));
+            if !probe_on_board || !found_on_board {
+                return Err(format!(
+                    "Trace step {} is off the board: {}",
+                    idx + 1,
+                    step.to_line()
+                ));
+            }
+        }
+        Ok(())
+    }
+    /// Get the words found before the given step, in order
+    pub fn found_until(&self, step: usize) -> Vec<WordPosition> {
+        self.steps
+            .iter()
+            .take(step)
+            .filter_map(|step| step.found)
+            .collect()
+    }
+    /// Get the matches found before the given step, in order, with the words read from the board
+    pub fn matches_until(&self, step: usize, board: &Board, trie: &Trie) -> Vec<Match> {
+        self.steps
+            .iter()
+            .take(step)
+            .filter(|step| step.found.is_some())
+            .filter_map(|step| {
+                Match::on_board(board, trie, step.position, step.direction, step.distance)
+            })
+            .collect()
+    }
+    /// Get the index of the step that found the given word
+    pub fn step_of(&self, word_position: &WordPosition) -> Option<usize> {
+        self.steps
+            .iter()
+            .position(|step| step.found.as_ref() == Some(word_position))
+    }
+}
+
+/// A cursor over a trace, moved forward and backward by the replay controls
+pub struct Replay {
+    pub trace: Trace,
+    /// Number of steps already played, from 0 to the length of the trace
+    pub position: usize,
+    /// Every word of the trace, read from the board once when the replay starts
+    pub matches: Vec<Match>,
+    // Index of the step that found each match
+    match_steps: Vec<usize>,
+}
+impl Replay {
+    pub fn new(trace: Trace, board: &Board, trie: &Trie) -> Self {
+        let (match_steps, matches) = trace
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.found.is_some())
+            .filter_map(|(idx, step)| {
+                let found =
+                    Match::on_board(board, trie, step.position, step.direction, step.distance);
+                found.map(|word_match| (idx, word_match))
+            })
+            .unzip();
+        Replay {
+            trace,
+            position: 0,
+            matches,
+            match_steps,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.trace.steps.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.trace.steps.is_empty()
+    }
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.len()
+    }
+    pub fn seek(&mut self, position: usize) {
+        self.position = usize::min(position, self.len());
+    }
+    pub fn forward(&mut self) {
+        self.seek(self.position + 1);
+    }
+    pub fn backward(&mut self) {
+        self.seek(self.position.saturating_sub(1));
+    }
+    /// The step that will be played next
+    pub fn current(&self) -> Option<&TraceStep> {
+        self.trace.steps.get(self.position)
+    }
+    /// The words found by the steps played so far
+    pub fn found(&self) -> Vec<WordPosition> {
+        self.trace.found_until(self.position)
+    }
+    /// The matches of the steps played so far, a prefix of `matches`
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::trace::{Replay, Trace};
+    /// use word_search_solver::trie::Trie;
+    /// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+    /// let trie = Trie::from(&vec!["ab", "db"]);
+    /// let mut replay = Replay::new(Trace::record(board.clone(), trie.clone()), &board, &trie);
+    /// assert_eq!(replay.matches.len(), 2);
+    /// assert!(replay.matches_played().is_empty());
+    /// replay.jump_to(&replay.matches[0].position());
+    /// assert_eq!(replay.matches_played(), &replay.matches[..1]);
+    /// ```
+    pub fn matches_played(&self) -> &[Match] {
+        let played = self
+            .match_steps
+            .partition_point(|&step| step < self.position);
+        &self.matches[..played]
+    }
+    /// Move right after the step that found the given word
+    pub fn jump_to(&mut self, word_position: &WordPosition) {
+        if let Some(step) = self.trace.step_of(word_position) {
+            self.seek(step + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::solve;
+
+    fn board() -> Board {
+        Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ])
+    }
+    #[test]
+    fn test_record_matches_solver() {
+        let trie = Trie::from(&vec!["abc", "aei", "fc"]);
+        let found = solve(&board(), &trie);
+        let trace = Trace::record(board(), trie.clone());
+        let positions: Vec<WordPosition> = found.iter().map(Match::position).collect();
+        assert_eq!(trace.found_until(trace.steps.len()), positions);
+        assert_eq!(
+            trace.matches_until(trace.steps.len(), &board(), &trie),
+            found
+        );
+        assert_eq!(trace.steps[0].position, (0, 0));
+        assert_eq!(trace.steps[0].direction, Direction::Up);
+        assert!(trace.steps.iter().any(|step| !step.feasible));
+    }
+    #[test]
+    fn test_validate_rejects_probes_past_the_edge() {
+        let mut trace = Trace::record(board(), Trie::from(&vec!["abc"]));
+        assert_eq!(trace.validate(&board()), Ok(()));
+        // Starts on the board, but reaches 2 cells past the right edge
+        trace.steps[3] = TraceStep::from_line("0 1 right 3 true").unwrap();
+        let error = trace.validate(&board()).unwrap_err();
+        assert_eq!(error, "Trace step 4 is off the board: 0 1 right 3 true");
+        trace.steps[3] = TraceStep::from_line("0 1 right -1 true").unwrap();
+        assert!(trace.validate(&board()).is_err());
+    }
+    #[test]
+    fn test_save_and_load() {
+        let trace = Trace::record(board(), Trie::from(&vec!["abc", "aei", "fc"]));
+        let path = std::env::temp_dir().join("word_search_trace_test.txt");
+        trace.save(&path).unwrap();
+        assert_eq!(Trace::load(&path).unwrap(), trace);
+        fs::write(&path, "0 0 up 0 maybe\n").unwrap();
+        assert!(Trace::load(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+    #[test]
+    fn test_replay_seek_and_jump() {
+        let trie = Trie::from(&vec!["abc", "fc"]);
+        let trace = Trace::record(board(), trie.clone());
+        let mut replay = Replay::new(trace, &board(), &trie);
+        assert!(replay.found().is_empty());
+        replay.backward();
+        assert_eq!(replay.position, 0);
+        let fc = WordPosition::new((1, 2), (0, 2));
+        replay.jump_to(&fc);
+        assert_eq!(replay.found().last(), Some(&fc));
+        replay.backward();
+        assert!(!replay.found().contains(&fc));
+        replay.seek(usize::MAX);
+        assert!(replay.is_finished());
+        assert_eq!(replay.found().len(), 2);
+        assert_eq!(replay.matches_played(), replay.matches.as_slice());
+    }
+}
@@ -1,7 +1,13 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TrieNode {
     children: HashMap<char, TrieNode>,
     is_word: bool,
+    // Position of the word in the list the trie was built from
+    index: usize,
 }
 
 impl TrieNode {
@@ -9,6 +15,7 @@ impl TrieNode {
         TrieNode {
             children: HashMap::new(),
             is_word: false,
+            index: 0,
         }
     }
 }
@@ -18,8 +25,12 @@ impl Default for TrieNode {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Trie {
     root: TrieNode,
+    // Words inserted so far, duplicates included
+    inserted: usize,
 }
 impl Default for Trie {
     fn default() -> Self {
@@ -30,6 +41,7 @@ impl Trie {
     pub fn new() -> Self {
         Trie {
             root: TrieNode::new(),
+            inserted: 0,
         }
     }
     pub fn from(words: &Vec<&str>) -> Self {
@@ -41,19 +53,19 @@ impl Trie {
         let mut current_node = &mut self.root;
 
         for c in word.chars() {
-            let next_node = current_node.children.entry(c).or_insert(TrieNode::new());
+            let next_node = current_node.children.entry(c).or_default();
             current_node = next_node;
         }
-        current_node.is_word = true;
+        // A word inserted twice keeps its first index
+        if !current_node.is_word {
+            current_node.is_word = true;
+            current_node.index = self.inserted;
+        }
+        self.inserted += 1;
     }
     pub fn insert_words(&mut self, words: &Vec<&str>) {
-        for word in words.to_owned().clone() {
-            let mut current_node = &mut self.root;
-            for c in word.chars() {
-                let next_node = current_node.children.entry(c).or_insert(TrieNode::new());
-                current_node = next_node;
-            }
-            current_node.is_word = true;
+        for word in words {
+            self.insert(word);
         }
     }
 
@@ -70,6 +82,58 @@ impl Trie {
         current_node.is_word
     }
 
+    /// Get the position of the word in the list the trie was built from, the first one if it is listed twice
+    /// # Example
+    /// ```
+    /// use word_search_solver::trie::Trie;
+    /// let trie = Trie::from(&vec!["one", "two", "one", "three"]);
+    /// assert_eq!(trie.index_of("three"), Some(3));
+    /// assert_eq!(trie.index_of("one"), Some(0));
+    /// assert_eq!(trie.index_of("on"), None);
+    /// ```
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        let mut current_node = &self.root;
+        for c in word.chars() {
+            current_node = current_node.children.get(&c)?;
+        }
+        current_node.is_word.then_some(current_node.index)
+    }
+
+    /// Count the distinct words of the trie
+    /// # Example
+    /// ```
+    /// use word_search_solver::trie::Trie;
+    /// assert_eq!(Trie::from(&vec!["ab", "abc", "b", "ab"]).word_count(), 3);
+    /// assert_eq!(Trie::new().word_count(), 0);
+    /// ```
+    pub fn word_count(&self) -> usize {
+        fn count(node: &TrieNode) -> usize {
+            node.is_word as usize + node.children.values().map(count).sum::<usize>()
+        }
+        count(&self.root)
+    }
+    /// Get the distinct words of the trie, sorted
+    /// # Example
+    /// ```
+    /// use word_search_solver::trie::Trie;
+    /// assert_eq!(Trie::from(&vec!["b", "ab", "abc", "ab"]).words(), vec!["ab", "abc", "b"]);
+    /// ```
+    pub fn words(&self) -> Vec<String> {
+        fn collect(node: &TrieNode, prefix: &mut String, words: &mut Vec<String>) {
+            if node.is_word {
+                words.push(prefix.clone());
+            }
+            for (c, child) in node.children.iter() {
+                prefix.push(*c);
+                collect(child, prefix, words);
+                prefix.pop();
+            }
+        }
+        let mut words = Vec::new();
+        collect(&self.root, &mut String::new(), &mut words);
+        words.sort();
+        words
+    }
     pub fn starts_with(&self, prefix: &str) -> bool {
         let mut current_node = &self.root;
 
@@ -131,4 +195,17 @@ mod tests {
         assert!(trie.search("Five"));
         assert!(!trie.search("Six"));
     }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let trie = Trie::from(&vec!["one", "two", "one", "three"]);
+        let json = serde_json::to_string(&trie).unwrap();
+        let restored: Trie = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.words(), trie.words());
+        assert_eq!(restored.index_of("three"), Some(3));
+        // Words inserted after a round trip keep counting from the original list
+        let mut restored = restored;
+        restored.insert("four");
+        assert_eq!(restored.index_of("four"), Some(4));
+    }
 }
@@ -0,0 +1,163 @@
+use crate::playback::Playback;
+use crate::render::ansi::word_color;
+use crate::strategy::SearchStrategy;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::Print;
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+const RESET: &str = "\x1b[0m";
+// Black on green, like the probe line of the ggez visualizer
+const PROBE: &str = "\x1b[1;30;42m";
+const STRIKE: &str = "\x1b[9m";
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+const HELP: &str = "space: pause/resume  right: step  +/-: speed  end: skip to end  q: quit";
+
+/// Build one frame of the terminal visualizer: the board with the current probe highlighted and
+/// the found words struck through, the list of found words on the right, then a status line
+pub fn frame_lines(strategy: &dyn SearchStrategy, playback: &Playback) -> Vec<String> {
+    let board = strategy.board();
+    let mut styles = vec![vec![None; board.get_cols()]; board.get_rows()];
+    for (idx, word_match) in strategy.found().iter().enumerate() {
+        for (i, j) in word_match.cells.iter() {
+            styles[*i][*j] = Some(format!("{}{}", word_color(idx), STRIKE));
+        }
+    }
+    if let Some(probe) = strategy.probe() {
+        for (i, j) in probe.cells() {
+            styles[i][j] = Some(PROBE.to_string());
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (i, row) in board.letters.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(j, letter)| match &styles[i][j] {
+                Some(style) => format!("{}{}{}", style, letter, RESET),
+                None => letter.to_string(),
+            })
+            .collect();
+        lines.push(cells.join(" "));
+    }
+    // Found words on the right of the board, extra lines below it when the list is longer
+    let grid_width = board.get_cols() * 2;
+    for (idx, word_match) in strategy.found().iter().enumerate() {
+        let entry = format!("{}{}{}", word_color(idx), word_match.word, RESET);
+        match lines.get_mut(idx) {
+            Some(line) => line.push_str(&format!("   {}", entry)),
+            None => lines.push(format!("{}  {}", " ".repeat(grid_width), entry)),
+        }
+    }
+
+    let status = if strategy.is_done() {
+        "finished".to_string()
+    } else if playback.paused {
+        "paused".to_string()
+    } else {
+        format!("running at {} steps/s", playback.steps_per_second)
+    };
+    lines.push(String::new());
+    lines.push(format!(
+        "{} | step {} | {} words found",
+        status,
+        strategy.steps(),
+        strategy.found().len()
+    ));
+    lines.push(HELP.to_string());
+    lines
+}
+
+// Put the terminal back in its normal state, even when the visualizer returns early
+struct TerminalGuard;
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn draw(stdout: &mut io::Stdout, lines: &[String]) -> io::Result<()> {
+    queue!(stdout, terminal::Clear(ClearType::All))?;
+    for (y, line) in lines.iter().enumerate() {
+        queue!(stdout, cursor::MoveTo(0, y as u16), Print(line))?;
+    }
+    stdout.flush()
+}
+
+/// Replay the search step by step in the terminal, until the user quits
+pub fn run(mut strategy: Box<dyn SearchStrategy>) -> io::Result<()> {
+    let _guard = TerminalGuard::enter()?;
+    let mut stdout = io::stdout();
+    let mut playback = Playback::new();
+    let mut last_step = Instant::now();
+    loop {
+        draw(&mut stdout, &frame_lines(strategy.as_ref(), &playback))?;
+        if event::poll(FRAME_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => playback.toggle_pause(),
+                    KeyCode::Right => {
+                        playback.paused = true;
+                        strategy.step();
+                    }
+                    KeyCode::Char('+') | KeyCode::Char('=') => playback.faster(),
+                    KeyCode::Char('-') => playback.slower(),
+                    KeyCode::End => strategy.finish(),
+                    _ => {}
+                }
+            }
+        }
+        if playback.paused {
+            last_step = Instant::now();
+            continue;
+        }
+        while !strategy.is_done() && last_step.elapsed() >= playback.step_interval() {
+            strategy.step();
+            last_step += playback.step_interval();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::solver::Searcher;
+    use crate::trie::Trie;
+
+    #[test]
+    fn test_frame_lines() {
+        let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+        let mut searcher = Searcher::new(board, Trie::from(&vec!["ab", "ad", "ac"]));
+        let playback = Playback::new();
+        let lines = frame_lines(&searcher, &playback);
+        // 2 rows, blank line, status and help
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with(&format!("{}a{}", PROBE, RESET)));
+        assert_eq!(lines[3], "running at 30 steps/s | step 0 | 0 words found");
+
+        SearchStrategy::finish(&mut searcher);
+        let lines = frame_lines(&searcher, &playback);
+        // The third word does not fit next to the board and goes on its own line
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].ends_with(&format!("{}ac{}", word_color(0), RESET)));
+        assert!(lines[2].starts_with("    "));
+        assert!(lines[2].contains("ad"));
+        assert!(lines[4].starts_with("finished"));
+    }
+}
@@ -1,7 +1,19 @@
-use crate::constant::*;
-use crate::state::main_state::MainState;
-use ggez::graphics::{self, Canvas, Color, Rect};
+use crate::board::Board;
+#[cfg(feature = "ggez")]
+use crate::game::Hint;
+#[cfg(feature = "ggez")]
+use crate::heatmap::Heatmap;
+#[cfg(feature = "ggez")]
+use crate::layout::Layout;
+#[cfg(feature = "ggez")]
+use crate::matches::Match;
+#[cfg(feature = "ggez")]
+use crate::theme::{Rgba, Theme};
+#[cfg(feature = "ggez")]
+use ggez::graphics::{self, Canvas, Color, Drawable, Rect};
+#[cfg(feature = "ggez")]
 use ggez::{glam::*, Context, GameResult};
+use std::error::Error;
 use std::fs;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -10,7 +22,7 @@ pub fn fetch_board(file_path: &Path) -> Vec<Vec<char>> {
     let file = File::open(file_path).unwrap();
     let reader = BufReader::new(file);
     let mut result = Vec::new();
-    for line in reader.lines().flatten() {
+    for line in reader.lines().map_while(Result::ok) {
         let mut vec_letter = Vec::new();
         for c in line.chars() {
             if c.is_alphabetic() {
@@ -19,20 +31,50 @@ pub fn fetch_board(file_path: &Path) -> Vec<Vec<char>> {
         }
         result.push(vec_letter.clone());
     }
-
-    println!("Letters: {:?}", result);
     result
 }
 
 pub fn fetch_target_words(file_path: &Path) -> Vec<String> {
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut result = Vec::new();
-    for word in contents.split(' ') {
-        result.push(word.to_owned());
-    }
-    result
+    read_target_words(file_path).expect("Should have been able to read the file")
+}
+
+/// Read a board file like `fetch_board`, a file that cannot be read or holds no board is an error
+/// naming the file
+/// # Example
+/// ```
+/// use std::path::Path;
+/// use word_search_solver::utils::read_board;
+/// let board = read_board(Path::new("src/input/board_months.txt")).unwrap();
+/// assert!(board.get_rows() > 0);
+/// assert!(read_board(Path::new("src/input/nowhere.txt")).is_err());
+/// ```
+pub fn read_board(file_path: &Path) -> Result<Board, Box<dyn Error>> {
+    let in_file = |error: &dyn Error| format!("{}: {}", file_path.display(), error);
+    let contents = fs::read_to_string(file_path).map_err(|error| in_file(&error))?;
+    let letters = contents
+        .lines()
+        .map(|line| line.chars().filter(|c| c.is_alphabetic()).collect())
+        .collect::<Vec<Vec<char>>>();
+    Board::try_from(letters).map_err(|error| format!("{}: {}", file_path.display(), error).into())
 }
-pub fn build_grid(ctx: &mut Context) -> graphics::Mesh {
+
+/// Read a word list like `fetch_target_words`, a file that cannot be read or is not UTF-8 is an
+/// error naming the file
+/// # Example
+/// ```
+/// use std::path::Path;
+/// use word_search_solver::utils::read_target_words;
+/// let words = read_target_words(Path::new("src/input/months.txt")).unwrap();
+/// assert_eq!(words.len(), 12);
+/// assert!(read_target_words(Path::new("src/input/nowhere.txt")).is_err());
+/// ```
+pub fn read_target_words(file_path: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let contents = fs::read_to_string(file_path)
+        .map_err(|error| format!("{}: {}", file_path.display(), error))?;
+    Ok(contents.split_whitespace().map(str::to_owned).collect())
+}
+#[cfg(feature = "ggez")]
+pub fn build_grid(ctx: &mut Context, layout: &Layout, theme: &Theme) -> graphics::Mesh {
     let mb = &mut graphics::MeshBuilder::new();
     // for i in 0..BOARD_SIZE {
     //     // Horizontal lines
@@ -65,47 +107,44 @@ pub fn build_grid(ctx: &mut Context) -> graphics::Mesh {
     mb.rectangle(
         graphics::DrawMode::stroke(1.0),
         graphics::Rect::new(
-            START_X,
-            START_Y,
-            GRID_SIZE * BOARD_SIZE as f32,
-            GRID_SIZE * BOARD_SIZE as f32,
+            layout.start_x,
+            layout.start_y,
+            layout.grid_width(),
+            layout.grid_height(),
         ),
-        graphics::Color::new(1.0, 0.0, 0.0, 1.0),
+        Color::from(theme.border),
     )
     .unwrap();
     graphics::Mesh::from_data(ctx, mb.build())
 }
 // Draw temporary strike through that disappear in the next frame
+#[cfg(feature = "ggez")]
 pub fn draw_highlighted_line(
     ctx: &mut Context,
+    layout: &Layout,
     start: Vec2,
     end: Vec2,
     canvas: &mut Canvas,
     feasible: bool,
+    theme: &Theme,
 ) {
     if !feasible {
         return;
     }
-    let start = Vec2::new(
-        START_X + GRID_SIZE * start.x + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * start.y + GRID_SIZE / 2.0,
-    );
-    let end = Vec2::new(
-        START_X + GRID_SIZE * end.x + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * end.y + GRID_SIZE / 2.0,
-    );
+    let start = Vec2::from(layout.cell_center(start.y as usize, start.x as usize));
+    let end = Vec2::from(layout.cell_center(end.y as usize, end.x as usize));
     let mb = &mut graphics::MeshBuilder::new();
     if start == end {
         mb.circle(
             graphics::DrawMode::fill(),
             start,
-            GRID_SIZE / 2.0,
+            layout.grid_size / 2.0,
             1.0,
-            Color::new(1.0, 0.0, 0.0, 0.5),
+            Color::from(theme.probe_cell),
         )
         .unwrap();
     } else {
-        mb.line(&[start, end], 10.0, Color::new(0.0, 1.0, 0.0, 1.0))
+        mb.line(&[start, end], 10.0, Color::from(theme.probe))
             .unwrap();
     }
     // Draw strike through
@@ -113,8 +152,10 @@ pub fn draw_highlighted_line(
     let line_meshes = graphics::Mesh::from_data(ctx, mb.build());
     canvas.draw(&line_meshes, graphics::DrawParam::default());
 }
+#[cfg(feature = "ggez")]
 pub fn draw_highlight(
     ctx: &mut Context,
+    layout: &Layout,
     start: Vec2,
     end: Vec2,
     canvas: &mut Canvas,
@@ -123,16 +164,15 @@ pub fn draw_highlight(
     if !feasible {
         return;
     }
-    let start_x = START_X + GRID_SIZE * 0 as f32;
-    let start_y = START_Y + GRID_SIZE * 0 as f32;
+    let (start_x, start_y) = layout.cell_origin(0, 0);
 
-    let rect = Rect::new(start_x, start_y, GRID_SIZE * 15.0, GRID_SIZE * 1.0);
+    let rect = Rect::new(start_x, start_y, layout.grid_width(), layout.grid_size);
     let mb = &mut graphics::MeshBuilder::new();
     if start == end {
         mb.circle(
             graphics::DrawMode::fill(),
             start,
-            GRID_SIZE / 2.0,
+            layout.grid_size / 2.0,
             1.0,
             Color::new(0.0, 0.0, 1.0, 1.0),
         )
@@ -150,38 +190,67 @@ pub fn draw_highlight(
             .offset([0.0, 0.0])
             .rotation(1.0), // .rotation(1.0),
     );
-} // Draw strike through that stay on the screen
-pub fn draw_line(
-    state: &mut MainState,
+}
+// Opacity of the strike capsules, the letters under them stay readable
+#[cfg(feature = "ggez")]
+const STRIKE_ALPHA: f32 = 0.45;
+#[cfg(feature = "ggez")]
+fn with_alpha(color: Rgba, alpha: f32) -> Color {
+    Color::new(color[0], color[1], color[2], color[3] * alpha)
+}
+// Outline of a capsule around the segment between two points, a circle when they are the same
+#[cfg(feature = "ggez")]
+fn capsule_points(start: Vec2, end: Vec2, radius: f32) -> Vec<Vec2> {
+    const SEGMENTS: usize = 12;
+    let along = (end - start).try_normalize().unwrap_or(Vec2::X);
+    let across = along.perp();
+    let mut points = Vec::with_capacity(2 * SEGMENTS + 2);
+    for (center, sign) in [(end, 1.0), (start, -1.0)] {
+        for k in 0..=SEGMENTS {
+            let angle = std::f32::consts::PI * k as f32 / SEGMENTS as f32;
+            points.push(center + (across * angle.cos() + along * angle.sin()) * radius * sign);
+        }
+    }
+    points
+}
+// Draw a capsule over every found word, in the color of its index in the word list
+#[cfg(feature = "ggez")]
+pub fn draw_strikes(
     ctx: &mut Context,
-    start_idx: usize,
-    end_idx: usize,
-    mb: &mut graphics::MeshBuilder,
     canvas: &mut Canvas,
+    layout: &Layout,
+    found: &[Match],
+    theme: &Theme,
 ) -> GameResult<()> {
-    let start = Vec2::new(
-        START_X + GRID_SIZE * (start_idx % BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * (start_idx / BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
-    );
-    let end = Vec2::new(
-        START_X + GRID_SIZE * (end_idx % BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * (end_idx / BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
-    );
-    mb.line(&[start, end], 5.0, Color::new(0.0, 0.0, 1.0, 1.0))?;
-    // Draw strike through
-    let line_mesh = graphics::Mesh::from_data(ctx, mb.build());
-    state.line_mesh = line_mesh;
-    canvas.draw(&state.line_mesh, graphics::DrawParam::new());
+    if found.is_empty() {
+        return Ok(());
+    }
+    let mb = &mut graphics::MeshBuilder::new();
+    for word_match in found {
+        let (start, end) = (word_match.start, word_match.end);
+        let start = Vec2::from(layout.cell_center(start.0, start.1));
+        let end = Vec2::from(layout.cell_center(end.0, end.1));
+        let color = with_alpha(theme.word_color(word_match.index), STRIKE_ALPHA);
+        let points = capsule_points(start, end, layout.grid_size * 0.35);
+        mb.polygon(graphics::DrawMode::fill(), &points, color)?;
+    }
+    let mesh = graphics::Mesh::from_data(ctx, mb.build());
+    canvas.draw(&mesh, graphics::DrawParam::default());
     Ok(())
 }
 // display out answer as we find words
-pub fn display_word(_ctx: &mut Context, canvas: &mut Canvas, word: &str, idx: usize) {
+#[cfg(feature = "ggez")]
+pub fn display_word(
+    _ctx: &mut Context,
+    canvas: &mut Canvas,
+    layout: &Layout,
+    word: &str,
+    idx: usize,
+    color: Color,
+) {
     let draw_params = graphics::DrawParam::new()
-        .dest([
-            START_X + GRID_SIZE * BOARD_SIZE as f32 + 100.0,
-            START_Y + 50.0 * idx as f32,
-        ])
-        .color(Color::new(0.0, 0.0, 0.0, 1.0));
+        .dest(Vec2::from(layout.word_list_pos(idx)))
+        .color(color);
     canvas.draw(
         graphics::Text::new(word)
             .set_scale(30.)
@@ -189,6 +258,251 @@ pub fn display_word(_ctx: &mut Context, canvas: &mut Canvas, word: &str, idx: us
         draw_params,
     );
 }
+// Draw a dot in the color of the strike capsule of a word, on the left of its entry in the list
+#[cfg(feature = "ggez")]
+pub fn display_swatch(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    layout: &Layout,
+    idx: usize,
+    color: Rgba,
+) {
+    let (x, y) = layout.word_list_pos(idx);
+    let swatch = graphics::Mesh::new_circle(
+        ctx,
+        graphics::DrawMode::fill(),
+        Vec2::new(x - 22.0, y + 18.0),
+        10.0,
+        0.5,
+        Color::from(color),
+    )
+    .unwrap();
+    canvas.draw(&swatch, graphics::DrawParam::default());
+}
+// Display a word of the play mode list that was found, greyed out and struck through
+#[cfg(feature = "ggez")]
+pub fn display_crossed_word(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    layout: &Layout,
+    word: &str,
+    idx: usize,
+    theme: &Theme,
+) {
+    let color = Color::from(theme.muted);
+    display_word(ctx, canvas, layout, word, idx, color);
+    let mut text = graphics::Text::new(word);
+    text.set_scale(30.).set_font("Montserrat");
+    let Some(size) = text.dimensions(ctx) else {
+        return;
+    };
+    let (x, y) = layout.word_list_pos(idx);
+    let strike = graphics::Mesh::new_line(
+        ctx,
+        &[
+            Vec2::new(x, y + size.h / 2.0),
+            Vec2::new(x + size.w, y + size.h / 2.0),
+        ],
+        3.0,
+        color,
+    )
+    .unwrap();
+    canvas.draw(&strike, graphics::DrawParam::default());
+}
+// Draw a wrong play mode selection fading out, between two points in pixel
+#[cfg(feature = "ggez")]
+pub fn draw_rejected_line(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    start: Vec2,
+    end: Vec2,
+    alpha: f32,
+    theme: &Theme,
+) {
+    let color = with_alpha(theme.error, alpha);
+    let mesh = if start == end {
+        graphics::Mesh::new_circle(ctx, graphics::DrawMode::fill(), start, 5.0, 0.5, color)
+    } else {
+        graphics::Mesh::new_line(ctx, &[start, end], 10.0, color)
+    };
+    canvas.draw(&mesh.unwrap(), graphics::DrawParam::default());
+}
+// Draw a play mode hint in the accent color of the theme, `alpha` makes the first letter flash
+#[cfg(feature = "ggez")]
+pub fn draw_hint(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    layout: &Layout,
+    hint: &Hint,
+    alpha: f32,
+    theme: &Theme,
+) {
+    let color = with_alpha(theme.accent, alpha);
+    let mb = &mut graphics::MeshBuilder::new();
+    match hint {
+        Hint::FirstLetter((i, j)) => {
+            let center = Vec2::from(layout.cell_center(*i, *j));
+            mb.circle(
+                graphics::DrawMode::fill(),
+                center,
+                layout.grid_size / 2.0,
+                1.0,
+                color,
+            )
+            .unwrap();
+        }
+        Hint::Direction((i, j), direction) => {
+            let center = Vec2::from(layout.cell_center(*i, *j));
+            mb.circle(
+                graphics::DrawMode::stroke(4.0),
+                center,
+                layout.grid_size / 2.0,
+                1.0,
+                color,
+            )
+            .unwrap();
+            // Point towards the second letter, the arrow may leave the grid for a single letter word
+            let diff = direction.to_coord_diff();
+            let next = center + Vec2::new(diff.1 as f32, diff.0 as f32) * layout.grid_size;
+            mb.line(&[center, next], 6.0, color).unwrap();
+        }
+        Hint::Placement(word_position) => {
+            let (start, end) = (word_position.start(), word_position.end());
+            let start = Vec2::from(layout.cell_center(start.0, start.1));
+            let end = Vec2::from(layout.cell_center(end.0, end.1));
+            mb.line(&[start, end], 10.0, color).unwrap();
+        }
+    }
+    let mesh = graphics::Mesh::from_data(ctx, mb.build());
+    canvas.draw(&mesh, graphics::DrawParam::default());
+}
+// Fill every cell with the color of its search effort
+#[cfg(feature = "ggez")]
+pub fn draw_heatmap(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    layout: &Layout,
+    heatmap: &Heatmap,
+    theme: &Theme,
+) {
+    let mb = &mut graphics::MeshBuilder::new();
+    for row in 0..layout.rows {
+        for col in 0..layout.cols {
+            let (x, y) = layout.cell_origin(row, col);
+            mb.rectangle(
+                graphics::DrawMode::fill(),
+                Rect::new(x, y, layout.grid_size, layout.grid_size),
+                Color::from(theme.heat_color(heatmap.intensity(row, col))),
+            )
+            .unwrap();
+        }
+    }
+    let mesh = graphics::Mesh::from_data(ctx, mb.build());
+    canvas.draw(&mesh, graphics::DrawParam::default());
+}
+// Draw the stats panel, one value per line
+#[cfg(feature = "ggez")]
+pub fn display_stats_panel(canvas: &mut Canvas, layout: &Layout, lines: &[String], theme: &Theme) {
+    let (x, y) = layout.stats_panel_pos();
+    for (idx, line) in lines.iter().enumerate() {
+        canvas.draw(
+            graphics::Text::new(line.as_str())
+                .set_scale(22.)
+                .set_font("Montserrat"),
+            graphics::DrawParam::new()
+                .dest([x, y + 32.0 * idx as f32])
+                .color(Color::from(theme.panel)),
+        );
+    }
+}
+// Draw the replay timeline under the grid, filled up to the current step
+#[cfg(feature = "ggez")]
+pub fn display_timeline(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    layout: &Layout,
+    position: usize,
+    len: usize,
+    theme: &Theme,
+) {
+    let (x, y, width, height) = layout.timeline_rect();
+    let progress = if len == 0 {
+        0.0
+    } else {
+        position as f32 / len as f32
+    };
+    let mb = &mut graphics::MeshBuilder::new();
+    mb.rectangle(
+        graphics::DrawMode::fill(),
+        Rect::new(x, y + height / 3.0, width, height / 3.0),
+        Color::from(theme.muted),
+    )
+    .unwrap();
+    mb.rectangle(
+        graphics::DrawMode::fill(),
+        Rect::new(x, y + height / 3.0, width * progress, height / 3.0),
+        Color::from(theme.accent),
+    )
+    .unwrap();
+    mb.circle(
+        graphics::DrawMode::fill(),
+        Vec2::new(x + width * progress, y + height / 2.0),
+        height / 2.0,
+        0.5,
+        Color::from(theme.accent),
+    )
+    .unwrap();
+    let mesh = graphics::Mesh::from_data(ctx, mb.build());
+    canvas.draw(&mesh, graphics::DrawParam::default());
+}
+// Draw the lines of the end of search summary in a box over the grid
+#[cfg(feature = "ggez")]
+pub fn display_summary(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    layout: &Layout,
+    lines: &[String],
+    theme: &Theme,
+) {
+    let (x, y) = layout.cell_origin(0, 0);
+    let margin = layout.grid_size;
+    let rect = Rect::new(
+        x + margin,
+        y + margin,
+        layout.grid_width() - 2.0 * margin,
+        40.0 * lines.len() as f32 + margin,
+    );
+    let background = graphics::Mesh::new_rectangle(
+        ctx,
+        graphics::DrawMode::fill(),
+        rect,
+        with_alpha(theme.background, 0.9),
+    )
+    .unwrap();
+    canvas.draw(&background, graphics::DrawParam::default());
+    let border = graphics::Mesh::new_rectangle(
+        ctx,
+        graphics::DrawMode::stroke(2.0),
+        rect,
+        Color::from(theme.text),
+    )
+    .unwrap();
+    canvas.draw(&border, graphics::DrawParam::default());
+    for (idx, line) in lines.iter().enumerate() {
+        canvas.draw(
+            graphics::Text::new(line.as_str())
+                .set_scale(if idx == 0 { 36. } else { 24. })
+                .set_font("Montserrat")
+                .set_bounds(Vec2::new(rect.w - margin, f32::INFINITY)),
+            graphics::DrawParam::new()
+                .dest([
+                    rect.x + margin / 2.0,
+                    rect.y + margin / 2.0 + 40.0 * idx as f32,
+                ])
+                .color(Color::from(theme.text)),
+        );
+    }
+}
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -0,0 +1,269 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::matches::Match;
+use crate::solver::solve;
+use crate::trie::Trie;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Declared placement of every word, one `word start_row start_col end_row end_col` line per word
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnswerKey {
+    pub placements: Vec<(String, WordPosition)>,
+}
+impl AnswerKey {
+    /// Parse an answer key, blank lines and lines starting with `#` are ignored
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::WordPosition;
+    /// use word_search_solver::verify::AnswerKey;
+    /// let key = AnswerKey::parse("# key\nabc 0 0 0 2\n").unwrap();
+    /// assert_eq!(key.placements, vec![("abc".to_string(), WordPosition::new((0, 0), (0, 2)))]);
+    /// assert!(AnswerKey::parse("abc 0 0 0").is_err());
+    /// ```
+    pub fn parse(contents: &str) -> Result<AnswerKey, String> {
+        let mut placements = Vec::new();
+        for (idx, line) in contents.lines().enumerate() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let invalid = || format!("Invalid answer on line {}: {}", idx + 1, line);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [word, cells @ ..] = &fields[..] else {
+                return Err(invalid());
+            };
+            let numbers: Vec<usize> = cells
+                .iter()
+                .map(|field| field.parse().ok())
+                .collect::<Option<_>>()
+                .filter(|numbers: &Vec<usize>| numbers.len() == 4)
+                .ok_or_else(invalid)?;
+            placements.push((
+                word.to_string(),
+                WordPosition::new((numbers[0], numbers[1]), (numbers[2], numbers[3])),
+            ));
+        }
+        Ok(AnswerKey { placements })
+    }
+    pub fn load(path: &Path) -> Result<AnswerKey, Box<dyn std::error::Error>> {
+        Ok(AnswerKey::parse(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// Something wrong with a puzzle and its answer key
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Problem {
+    /// A cell of the placement is not on the board
+    OutOfBoard(String, WordPosition),
+    /// The cells are not on the same row, column or diagonal
+    NotALine(String, WordPosition),
+    /// The placement goes in a direction the puzzle does not allow
+    DirectionNotAllowed(String, WordPosition, Direction),
+    /// The placement spells something else, given as the last field
+    Misspelled(String, WordPosition, String),
+    /// The answer key has a word that is not in the word list
+    NotInWordList(String),
+    /// A word of the list has no placement in the answer key
+    MissingFromKey(String),
+    /// The solver found the word somewhere the answer key does not say
+    ExtraPlacement(Match),
+}
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells = |word_position: &WordPosition| {
+            format!("{:?} to {:?}", word_position.start(), word_position.end())
+        };
+        match self {
+            Problem::OutOfBoard(word, placement) => {
+                write!(f, "{}: {} is off the board", word, cells(placement))
+            }
+            Problem::NotALine(word, placement) => {
+                write!(f, "{}: {} is not a straight line", word, cells(placement))
+            }
+            Problem::DirectionNotAllowed(word, placement, direction) => write!(
+                f,
+                "{}: {} goes {}, which is not allowed",
+                word,
+                cells(placement),
+                direction.name()
+            ),
+            Problem::Misspelled(word, placement, spelled) => {
+                write!(f, "{}: {} spells {}", word, cells(placement), spelled)
+            }
+            Problem::NotInWordList(word) => write!(f, "{}: not in the word list", word),
+            Problem::MissingFromKey(word) => write!(f, "{}: not in the answer key", word),
+            Problem::ExtraPlacement(word_match) => write!(
+                f,
+                "{}: also found at {}, which the answer key does not list",
+                word_match.word,
+                cells(&word_match.position())
+            ),
+        }
+    }
+}
+
+/// Check every placement of the answer key, then run the solver to make sure the words
+/// are nowhere else on the board
+/// # Arguments
+/// * `board` - The board of the puzzle
+/// * `words` - The word list of the puzzle
+/// * `key` - The declared placements
+/// * `directions` - The directions words may go in
+/// # Returns
+/// * `Vec<Problem>` - Every problem found, empty when the answer key is right
+/// # Example
+/// ```
+/// use word_search_solver::board::{Board, Direction, WordPosition};
+/// use word_search_solver::verify::{verify, AnswerKey, Problem};
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+/// let words = vec!["abc".to_string(), "fc".to_string()];
+/// let key = AnswerKey::parse("abc 0 0 0 2\nfc 1 2 0 2").unwrap();
+/// let all: Vec<Direction> = Direction::iterator().copied().collect();
+/// assert!(verify(&board, &words, &key, &all).is_empty());
+/// let forward = [Direction::Right, Direction::Down, Direction::DownRight];
+/// let fc = WordPosition::new((1, 2), (0, 2));
+/// assert_eq!(
+///     verify(&board, &words, &key, &forward),
+///     vec![Problem::DirectionNotAllowed("fc".to_string(), fc, Direction::Up)]
+/// );
+/// ```
+pub fn verify(
+    board: &Board,
+    words: &[String],
+    key: &AnswerKey,
+    directions: &[Direction],
+) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (word, placement) in key.placements.iter() {
+        if !words.contains(word) {
+            problems.push(Problem::NotInWordList(word.clone()));
+            continue;
+        }
+        let (start, end) = (placement.start(), placement.end());
+        if [start, end]
+            .iter()
+            .any(|(i, j)| *i >= board.get_rows() || *j >= board.get_cols())
+        {
+            problems.push(Problem::OutOfBoard(word.clone(), *placement));
+            continue;
+        }
+        // A single letter has no direction to check
+        if start != end {
+            let Some(direction) = Direction::between(start, end) else {
+                problems.push(Problem::NotALine(word.clone(), *placement));
+                continue;
+            };
+            if !directions.contains(&direction) {
+                problems.push(Problem::DirectionNotAllowed(
+                    word.clone(),
+                    *placement,
+                    direction,
+                ));
+            }
+        }
+        let (start_idx, end_idx) = placement.to_1d(board.get_cols());
+        let spelled = board.get_word_from_1d_position(start_idx, end_idx);
+        if spelled != *word {
+            problems.push(Problem::Misspelled(word.clone(), *placement, spelled));
+        }
+    }
+    for word in words {
+        if !key.placements.iter().any(|(key_word, _)| key_word == word) {
+            problems.push(Problem::MissingFromKey(word.clone()));
+        }
+    }
+
+    // A palindrome or a single letter is found several times on the same cells
+    let same_cells = |a: &WordPosition, b: &Match| {
+        (a.start(), a.end()) == (b.start, b.end) || (a.start(), a.end()) == (b.end, b.start)
+    };
+    let trie = Trie::from(&words.iter().map(String::as_str).collect());
+    let mut extra: Vec<Match> = Vec::new();
+    for found in solve(board, &trie) {
+        let declared = key
+            .placements
+            .iter()
+            .any(|(word, placement)| *word == found.word && same_cells(placement, &found));
+        let listed = extra
+            .iter()
+            .any(|listed| listed.word == found.word && same_cells(&listed.position(), &found));
+        if !declared && !listed && directions.contains(&found.direction) {
+            extra.push(found);
+        }
+    }
+    problems.extend(extra.into_iter().map(Problem::ExtraPlacement));
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_kind_of_problem() {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'a'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let words = ["ab", "aba", "dei", "fi", "hg"].map(String::from).to_vec();
+        let key = AnswerKey::parse(
+            "ab 0 0 0 1\naba 0 2 0 0\ndei 1 0 2 2\nfi 1 2 3 2\nzz 0 0 0 1\nhg 2 1 2 2",
+        )
+        .unwrap();
+        let all: Vec<Direction> = Direction::iterator().copied().collect();
+        let problems = verify(&board, &words, &key, &all);
+        assert_eq!(
+            problems[..4],
+            [
+                Problem::NotALine("dei".into(), WordPosition::new((1, 0), (2, 2))),
+                Problem::OutOfBoard("fi".into(), WordPosition::new((1, 2), (3, 2))),
+                Problem::NotInWordList("zz".into()),
+                Problem::Misspelled("hg".into(), WordPosition::new((2, 1), (2, 2)), "hi".into()),
+            ]
+        );
+        let extra: Vec<(&str, WordPosition)> = problems[4..]
+            .iter()
+            .map(|problem| match problem {
+                Problem::ExtraPlacement(word_match) => {
+                    (word_match.word.as_str(), word_match.position())
+                }
+                _ => panic!("{} is not an extra placement", problem),
+            })
+            .collect();
+        assert_eq!(
+            extra,
+            vec![
+                // "aba" reads both ways on the same cells, only "ab" is really there twice
+                ("ab", WordPosition::new((0, 2), (0, 1))),
+                // The real placements of the words the key got wrong
+                ("fi", WordPosition::new((1, 2), (2, 2))),
+                ("hg", WordPosition::new((2, 1), (2, 0))),
+            ]
+        );
+        assert_eq!(
+            problems[4].to_string(),
+            "ab: also found at (0, 2) to (0, 1), which the answer key does not list"
+        );
+        // The second "ab" goes left, which does not count when only forward words are allowed
+        let forward = [Direction::Right, Direction::Down, Direction::DownRight];
+        let problems = verify(&board, &words, &key, &forward);
+        assert_eq!(problems.len(), 6);
+        assert!(problems.contains(&Problem::DirectionNotAllowed(
+            "aba".into(),
+            WordPosition::new((0, 2), (0, 0)),
+            Direction::Left
+        )));
+        assert_eq!(
+            problems.last().map(ToString::to_string),
+            Some(
+                "fi: also found at (1, 2) to (2, 2), which the answer key does not list"
+                    .to_string()
+            )
+        );
+    }
+}